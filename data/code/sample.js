@@ -0,0 +1,75 @@
+const EventEmitter = require("events");
+
+class Cache extends EventEmitter {
+  constructor(limit = 100) {
+    super();
+    this.limit = limit;
+    this.entries = new Map();
+  }
+
+  get(key) {
+    if (!this.entries.has(key)) {
+      this.emit("miss", key);
+      return undefined;
+    }
+    const value = this.entries.get(key);
+    this.entries.delete(key);
+    this.entries.set(key, value);
+    return value;
+  }
+
+  set(key, value) {
+    if (this.entries.has(key)) {
+      this.entries.delete(key);
+    } else if (this.entries.size >= this.limit) {
+      const oldest = this.entries.keys().next().value;
+      this.entries.delete(oldest);
+      this.emit("evict", oldest);
+    }
+    this.entries.set(key, value);
+    return this;
+  }
+}
+
+function debounce(fn, wait = 250) {
+  let timer = null;
+  return (...args) => {
+    clearTimeout(timer);
+    timer = setTimeout(() => fn.apply(null, args), wait);
+  };
+}
+
+async function fetchJson(url, { retries = 3, timeout = 5000 } = {}) {
+  for (let attempt = 1; attempt <= retries; attempt++) {
+    const controller = new AbortController();
+    const timer = setTimeout(() => controller.abort(), timeout);
+    try {
+      const response = await fetch(url, { signal: controller.signal });
+      if (!response.ok) {
+        throw new Error(`HTTP ${response.status} for ${url}`);
+      }
+      return await response.json();
+    } catch (err) {
+      if (attempt === retries) throw err;
+    } finally {
+      clearTimeout(timer);
+    }
+  }
+}
+
+const groupBy = (items, keyFn) =>
+  items.reduce((groups, item) => {
+    const key = keyFn(item);
+    (groups[key] ||= []).push(item);
+    return groups;
+  }, {});
+
+function chunk(array, size) {
+  const result = [];
+  for (let i = 0; i < array.length; i += size) {
+    result.push(array.slice(i, i + size));
+  }
+  return result;
+}
+
+module.exports = { Cache, debounce, fetchJson, groupBy, chunk };
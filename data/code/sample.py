@@ -0,0 +1,88 @@
+import json
+import os
+from collections import defaultdict
+from dataclasses import dataclass, field
+
+
+@dataclass
+class Task:
+    name: str
+    priority: int = 0
+    tags: list[str] = field(default_factory=list)
+    done: bool = False
+
+    def to_dict(self) -> dict:
+        return {
+            "name": self.name,
+            "priority": self.priority,
+            "tags": self.tags,
+            "done": self.done,
+        }
+
+
+class TaskList:
+    def __init__(self, path: str):
+        self.path = path
+        self.tasks: list[Task] = []
+        if os.path.exists(path):
+            self.load()
+
+    def load(self) -> None:
+        with open(self.path, "r", encoding="utf-8") as f:
+            data = json.load(f)
+        self.tasks = [Task(**item) for item in data]
+
+    def save(self) -> None:
+        with open(self.path, "w", encoding="utf-8") as f:
+            json.dump([t.to_dict() for t in self.tasks], f, indent=2)
+
+    def add(self, name: str, priority: int = 0, *tags: str) -> Task:
+        task = Task(name=name, priority=priority, tags=list(tags))
+        self.tasks.append(task)
+        return task
+
+    def pending(self) -> list[Task]:
+        return sorted(
+            (t for t in self.tasks if not t.done),
+            key=lambda t: (-t.priority, t.name),
+        )
+
+    def by_tag(self) -> dict[str, list[Task]]:
+        groups = defaultdict(list)
+        for task in self.tasks:
+            for tag in task.tags or ["untagged"]:
+                groups[tag].append(task)
+        return dict(groups)
+
+
+def fibonacci(n: int) -> list[int]:
+    if n <= 0:
+        return []
+    seq = [0, 1]
+    while len(seq) < n:
+        seq.append(seq[-1] + seq[-2])
+    return seq[:n]
+
+
+def merge_sorted(left: list[int], right: list[int]) -> list[int]:
+    result = []
+    i = j = 0
+    while i < len(left) and j < len(right):
+        if left[i] <= right[j]:
+            result.append(left[i])
+            i += 1
+        else:
+            result.append(right[j])
+            j += 1
+    result.extend(left[i:])
+    result.extend(right[j:])
+    return result
+
+
+if __name__ == "__main__":
+    todo = TaskList("tasks.json")
+    todo.add("write tests", 2, "dev")
+    todo.add("review pull request", 1, "dev", "team")
+    for task in todo.pending():
+        print(f"[{task.priority}] {task.name} ({', '.join(task.tags)})")
+    todo.save()
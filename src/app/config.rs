@@ -0,0 +1,95 @@
+use std::fs;
+use super::paths;
+use super::state::Settings;
+
+/// Loads settings from the user's config file.
+///
+/// A missing file silently yields the defaults. A file that cannot be read or
+/// parsed (including unknown keys) also yields the defaults, together with a
+/// warning to show to the user. Numbers outside the ranges the Settings
+/// screen allows are clamped into them.
+pub fn load_settings() -> (Settings, Option<String>) {
+    let Some(path) = paths::settings_file() else {
+        return (Settings::default(), None);
+    };
+    if !path.exists() {
+        return (Settings::default(), None);
+    }
+
+    let result = fs::read_to_string(&path)
+        .map_err(|e| e.to_string())
+        .and_then(|contents| {
+            toml::from_str::<Settings>(&contents).map_err(|e| describe_toml_error(&e, &contents))
+        });
+
+    match result {
+        Ok(settings) => (settings.clamped(), None),
+        Err(reason) => {
+            let warning = format!(
+                "Ignoring {} ({}), using default settings",
+                path.display(),
+                reason
+            );
+            (Settings::default(), Some(warning))
+        }
+    }
+}
+
+/// Condenses a multi-line TOML error into "line N: message".
+pub fn describe_toml_error(err: &toml::de::Error, contents: &str) -> String {
+    let message = err.message().trim();
+    match err.span() {
+        Some(span) => {
+            let line = contents[..span.start.min(contents.len())].matches('\n').count() + 1;
+            format!("line {}: {}", line, message)
+        }
+        None => message.to_string(),
+    }
+}
+
+/// `saved` with every setting that differs between `before` and `after`
+/// taken from `after`.
+pub fn merge_changes(
+    saved: &Settings,
+    before: &Settings,
+    after: &Settings,
+) -> Result<Settings, Box<dyn std::error::Error>> {
+    let before = toml::Table::try_from(before)?;
+    let after = toml::Table::try_from(after)?;
+    let mut merged = toml::Table::try_from(saved)?;
+    for (key, value) in after {
+        if before.get(&key) != Some(&value) {
+            merged.insert(key, value);
+        }
+    }
+    Ok(merged.try_into()?)
+}
+
+pub fn save_settings(settings: &Settings) -> Result<(), Box<dyn std::error::Error>> {
+    let path = paths::settings_file().ok_or("Could not determine config directory")?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&path, toml::to_string_pretty(settings)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::TestMode;
+
+    #[test]
+    fn merge_keeps_unchanged_settings_as_saved() {
+        let saved = Settings { language: "klingon".to_string(), test_mode: TestMode::Custom, ..Settings::default() };
+        // This session fell back to another language and took a --time override
+        let before = Settings { time_limit: 120, test_mode: TestMode::Time, ..Settings::default() };
+        let after = Settings { word_count: 60, ..before.clone() };
+
+        let merged = merge_changes(&saved, &before, &after).unwrap();
+        assert_eq!(merged.word_count, 60);
+        assert_eq!(merged.time_limit, saved.time_limit);
+        assert_eq!(merged.test_mode, TestMode::Custom);
+        assert_eq!(merged.language, "klingon");
+    }
+}
@@ -0,0 +1,65 @@
+/// User-supplied practice text, from a file or stdin.
+#[derive(Debug, Clone)]
+pub struct CustomText {
+    /// File name, or "stdin".
+    pub source: String,
+    pub raw: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CustomOptions {
+    pub normalize_whitespace: bool,
+    pub ascii_only: bool,
+    /// Words per section, or 0 to type the whole text at once.
+    pub section_words: usize,
+}
+
+impl CustomText {
+    /// Cleans up the raw text according to `options` and splits it into sections.
+    pub fn sections(&self, options: &CustomOptions) -> Vec<String> {
+        let mut text: String = if options.ascii_only {
+            self.raw.chars().filter(|c| c.is_ascii()).collect()
+        } else {
+            self.raw.clone()
+        };
+        if options.normalize_whitespace {
+            text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        }
+
+        let sections = if options.section_words == 0 {
+            vec![text.trim().to_string()]
+        } else {
+            split_every(&text, options.section_words)
+        };
+        sections.into_iter().filter(|s| !s.is_empty()).collect()
+    }
+}
+
+/// Splits `text` into chunks of `words` words, keeping the original
+/// whitespace inside each chunk.
+fn split_every(text: &str, words: usize) -> Vec<String> {
+    let mut sections = Vec::new();
+    let mut start = None;
+    let mut count = 0;
+    let mut prev_is_space = true;
+
+    for (i, ch) in text.char_indices() {
+        let is_space = ch.is_whitespace();
+        if !is_space && prev_is_space {
+            if count == words {
+                if let Some(s) = start {
+                    sections.push(text[s..i].trim_end().to_string());
+                }
+                start = None;
+                count = 0;
+            }
+            start.get_or_insert(i);
+            count += 1;
+        }
+        prev_is_space = is_space;
+    }
+    if let Some(s) = start {
+        sections.push(text[s..].trim_end().to_string());
+    }
+    sections
+}
@@ -0,0 +1,389 @@
+/// What happened to a single character of the test text.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CharState {
+    /// Not typed yet.
+    Pending,
+    Correct,
+    /// Typed wrong and not fixed.
+    Incorrect,
+    /// Typed wrong at some point, then fixed.
+    Corrected,
+    /// Skipped over without being typed.
+    Missed,
+}
+
+#[derive(Debug, Clone)]
+pub struct TypedChar {
+    pub expected: char,
+    pub typed: Option<char>,
+    pub state: CharState,
+    had_error: bool,
+}
+
+/// One word of the test text and everything typed against it.
+#[derive(Debug, Clone)]
+pub struct Word {
+    pub chars: Vec<TypedChar>,
+    /// Letters typed past the end of the word.
+    pub extra: Vec<char>,
+    /// Whitespace between this word and the next; empty for the last word.
+    pub separator: String,
+}
+
+impl Word {
+    fn has_errors(&self) -> bool {
+        !self.extra.is_empty()
+            || self
+                .chars
+                .iter()
+                .any(|c| matches!(c.state, CharState::Incorrect | CharState::Missed))
+    }
+
+    fn is_correct(&self) -> bool {
+        self.extra.is_empty()
+            && self
+                .chars
+                .iter()
+                .all(|c| matches!(c.state, CharState::Correct | CharState::Corrected))
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CharCounts {
+    pub correct: usize,
+    pub incorrect: usize,
+    pub corrected: usize,
+    pub extra: usize,
+    pub missed: usize,
+}
+
+/// Overflow letters allowed per word before further letters are ignored.
+const MAX_EXTRA: usize = 20;
+
+/// Tracks the state of every word of the test text as the user types.
+///
+/// Whitespace moves on to the next word, skipping whatever is left of the
+/// current one, including any indentation after a line break. Letters past
+/// the end of a word are kept as extra letters.
+#[derive(Debug, Clone, Default)]
+pub struct TypingEngine {
+    words: Vec<Word>,
+    /// Index of the word being typed.
+    word: usize,
+    /// Number of letters typed into the current word, extras excluded.
+    letter: usize,
+}
+
+impl TypingEngine {
+    pub fn new(text: &str) -> Self {
+        let mut words: Vec<Word> = Vec::new();
+        for ch in text.chars() {
+            if ch.is_whitespace() {
+                // Whitespace before the first word is not part of the test
+                if let Some(word) = words.last_mut() {
+                    word.separator.push(ch);
+                }
+            } else {
+                let starts_word = words.last().is_none_or(|w| !w.separator.is_empty());
+                if starts_word {
+                    words.push(Word {
+                        chars: Vec::new(),
+                        extra: Vec::new(),
+                        separator: String::new(),
+                    });
+                }
+                words.last_mut().unwrap().chars.push(TypedChar {
+                    expected: ch,
+                    typed: None,
+                    state: CharState::Pending,
+                    had_error: false,
+                });
+            }
+        }
+        Self { words, word: 0, letter: 0 }
+    }
+
+    pub fn words(&self) -> &[Word] {
+        &self.words
+    }
+
+    /// Position of the caret as (word index, letter index within the word).
+    pub fn caret(&self) -> (usize, usize) {
+        (self.word, self.letter)
+    }
+
+    /// Number of characters of the test text before the caret.
+    pub fn pos(&self) -> usize {
+        let before: usize = self.words[..self.word.min(self.words.len())]
+            .iter()
+            .map(|w| w.chars.len() + w.separator.chars().count())
+            .sum();
+        before + self.letter
+    }
+
+    /// The caret `pos` characters into the text, in the form `caret()` uses.
+    pub fn caret_at(&self, pos: usize) -> (usize, usize) {
+        let mut remaining = pos;
+        for (i, word) in self.words.iter().enumerate() {
+            let width = word.chars.len() + word.separator.chars().count();
+            if remaining < width {
+                return (i, remaining.min(word.chars.len()));
+            }
+            remaining -= width;
+        }
+        (self.words.len(), 0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.words
+            .iter()
+            .map(|w| w.chars.len() + w.separator.chars().count())
+            .sum()
+    }
+
+    /// The character the caret is on: a letter, the separator after a fully
+    /// typed word, or `None` once the text is done.
+    pub fn expected(&self) -> Option<char> {
+        let word = self.words.get(self.word)?;
+        match word.chars.get(self.letter) {
+            Some(slot) => Some(slot.expected),
+            None => word.separator.chars().next(),
+        }
+    }
+
+    pub fn is_complete(&self) -> bool {
+        match self.words.len().checked_sub(1) {
+            None => true,
+            Some(last) => {
+                self.word > last
+                    || (self.word == last
+                        && self.letter == self.words[last].chars.len()
+                        && self.words[last].is_correct())
+            }
+        }
+    }
+
+    /// Types `c` at the caret. Returns whether it was right, or `None` when
+    /// the key had no effect.
+    pub fn type_char(&mut self, c: char) -> Option<bool> {
+        if self.is_complete() {
+            return None;
+        }
+        let word = &mut self.words[self.word];
+
+        if c.is_whitespace() {
+            // Nothing to skip at the start of a word
+            if self.letter == 0 && word.extra.is_empty() {
+                return None;
+            }
+            let complete = self.letter == word.chars.len();
+            for slot in word.chars[self.letter..].iter_mut() {
+                slot.state = CharState::Missed;
+            }
+            // Line breaks need Enter and spaces need Space; either ends the last word
+            let right_key = word.separator.is_empty() || word.separator.contains('\n') == (c == '\n');
+            let correct = complete && word.is_correct() && right_key;
+            self.word += 1;
+            self.letter = 0;
+            return Some(correct);
+        }
+
+        match word.chars.get_mut(self.letter) {
+            Some(slot) => {
+                let correct = c == slot.expected;
+                slot.typed = Some(c);
+                slot.state = match (correct, slot.had_error) {
+                    (true, false) => CharState::Correct,
+                    (true, true) => CharState::Corrected,
+                    (false, _) => CharState::Incorrect,
+                };
+                slot.had_error |= !correct;
+                self.letter += 1;
+                Some(correct)
+            }
+            None if word.extra.len() < MAX_EXTRA => {
+                word.extra.push(c);
+                Some(false)
+            }
+            None => None,
+        }
+    }
+
+    /// Removes the last thing typed. Backspacing from the start of a word
+    /// returns into the previous word, but only if it has errors to fix.
+    pub fn backspace(&mut self) -> bool {
+        let Some(word) = self.words.get_mut(self.word) else {
+            return false;
+        };
+
+        if word.extra.pop().is_some() {
+            return true;
+        }
+        if self.letter > 0 {
+            self.letter -= 1;
+            let slot = &mut word.chars[self.letter];
+            slot.typed = None;
+            slot.state = CharState::Pending;
+            return true;
+        }
+        if self.word == 0 || !self.words[self.word - 1].has_errors() {
+            return false;
+        }
+
+        self.word -= 1;
+        let previous = &mut self.words[self.word];
+        for slot in previous.chars.iter_mut().filter(|c| c.state == CharState::Missed) {
+            slot.state = CharState::Pending;
+        }
+        self.letter = previous.chars.iter().take_while(|c| c.typed.is_some()).count();
+        true
+    }
+
+    /// The text typed so far, with a single space between words.
+    pub fn input(&self) -> String {
+        let mut input = String::new();
+        for (i, word) in self.words.iter().enumerate().take(self.word + 1) {
+            if i > 0 {
+                input.push(' ');
+            }
+            input.extend(word.chars.iter().filter_map(|c| c.typed));
+            input.extend(word.extra.iter());
+        }
+        input
+    }
+
+    /// Words moved past, plus the last word once the text is complete.
+    pub fn words_typed(&self) -> usize {
+        if self.is_complete() {
+            self.words.len()
+        } else {
+            self.word
+        }
+    }
+
+    /// Characters that count towards net WPM: correct letters plus the
+    /// space after every correctly typed word.
+    pub fn correct_chars(&self) -> usize {
+        let counts = self.counts();
+        let correct_words = self.words[..self.word.min(self.words.len())]
+            .iter()
+            .filter(|w| w.is_correct())
+            .count();
+        counts.correct + counts.corrected + correct_words
+    }
+
+    pub fn counts(&self) -> CharCounts {
+        let mut counts = CharCounts::default();
+        for word in &self.words {
+            counts.extra += word.extra.len();
+            for slot in &word.chars {
+                match slot.state {
+                    CharState::Pending => {}
+                    CharState::Correct => counts.correct += 1,
+                    CharState::Incorrect => counts.incorrect += 1,
+                    CharState::Corrected => counts.corrected += 1,
+                    CharState::Missed => counts.missed += 1,
+                }
+            }
+        }
+        counts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn typed(text: &str, keys: &str) -> TypingEngine {
+        let mut engine = TypingEngine::new(text);
+        for c in keys.chars() {
+            engine.type_char(c);
+        }
+        engine
+    }
+
+    #[test]
+    fn space_skips_rest_of_word_as_missed() {
+        let mut engine = typed("abc def", "a");
+        assert_eq!(engine.type_char(' '), Some(false));
+        let states: Vec<CharState> = engine.words()[0].chars.iter().map(|c| c.state).collect();
+        assert_eq!(states, [CharState::Correct, CharState::Missed, CharState::Missed]);
+        assert_eq!(engine.caret(), (1, 0));
+        assert_eq!(engine.pos(), 4);
+    }
+
+    #[test]
+    fn space_at_start_of_word_does_nothing() {
+        let mut engine = typed("ab cd", "ab ");
+        assert_eq!(engine.type_char(' '), None);
+        assert_eq!(engine.caret(), (1, 0));
+    }
+
+    #[test]
+    fn overflow_letters_are_capped() {
+        let mut engine = typed("ab cd", "ab");
+        assert_eq!(engine.type_char('x'), Some(false));
+        for _ in 1..MAX_EXTRA {
+            engine.type_char('x');
+        }
+        assert_eq!(engine.type_char('x'), None);
+        assert_eq!(engine.words()[0].extra.len(), MAX_EXTRA);
+        assert_eq!(engine.counts().extra, MAX_EXTRA);
+        assert_eq!(engine.type_char(' '), Some(false));
+    }
+
+    #[test]
+    fn backspace_returns_only_into_a_word_with_errors() {
+        let mut clean = typed("ab cd", "ab ");
+        assert!(!clean.backspace());
+        assert_eq!(clean.caret(), (1, 0));
+
+        let mut wrong = typed("ab cd", "ax ");
+        assert!(wrong.backspace());
+        assert_eq!(wrong.caret(), (0, 2));
+
+        let mut skipped = typed("abc d", "a ");
+        assert!(skipped.backspace());
+        assert_eq!(skipped.caret(), (0, 1));
+        assert_eq!(skipped.words()[0].chars[1].state, CharState::Pending);
+    }
+
+    #[test]
+    fn fixed_mistake_is_corrected() {
+        let mut engine = typed("ab", "ax");
+        engine.backspace();
+        engine.type_char('b');
+        assert_eq!(engine.words()[0].chars[1].state, CharState::Corrected);
+        assert!(engine.is_complete());
+    }
+
+    #[test]
+    fn enter_skips_line_break_and_indentation() {
+        let mut engine = typed("fn {\n    x", "fn {");
+        assert_eq!(engine.type_char('\n'), Some(true));
+        assert_eq!(engine.caret(), (2, 0));
+        assert_eq!(engine.pos(), 9);
+        assert_eq!(engine.expected(), Some('x'));
+
+        let mut spaced = typed("fn {\n    x", "fn {");
+        assert_eq!(spaced.type_char(' '), Some(false));
+    }
+
+    #[test]
+    fn leading_whitespace_is_ignored() {
+        let engine = TypingEngine::new("  ab");
+        assert_eq!(engine.len(), 2);
+        assert_eq!(engine.expected(), Some('a'));
+    }
+
+    #[test]
+    fn correct_chars_count_letters_and_spaces_of_correct_words() {
+        let engine = typed("ab cd ef", "ab cx");
+        // a, b and c, plus the space after "ab"
+        assert_eq!(engine.correct_chars(), 4);
+
+        let engine = typed("ab cd ef", "ax cd ");
+        // "ax" is wrong, so only its "a" and the letters and space of "cd" count
+        assert_eq!(engine.correct_chars(), 4);
+    }
+}
@@ -0,0 +1,164 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use serde::{Serialize, Serializer};
+use serde_json::Value;
+use super::history::{self, RunRecord};
+use super::paths;
+
+/// Version of the export schema, bumped whenever a column changes meaning or
+/// is removed. New columns are only ever added at the end.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Export columns, in CSV order. Must match the fields of `ExportRow`.
+pub const COLUMNS: [&str; 19] = [
+    "timestamp",
+    "date",
+    "mode",
+    "language",
+    "word_count",
+    "time_limit",
+    "wpm",
+    "raw_wpm",
+    "accuracy",
+    "consistency",
+    "errors",
+    "corrected_errors",
+    "words_typed",
+    "duration_secs",
+    "quote_id",
+    "source",
+    "symbol_accuracy",
+    "imported_from",
+    "seed",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+        }
+    }
+
+    /// Guesses the format from a file name, e.g. `runs.json`.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "csv" => Some(ExportFormat::Csv),
+            "json" => Some(ExportFormat::Json),
+            _ => None,
+        }
+    }
+}
+
+/// One run as exported. Unlike `RunRecord`, every field is always present.
+#[derive(Debug, Serialize)]
+struct ExportRow<'a> {
+    timestamp: u64,
+    date: String,
+    mode: &'static str,
+    language: &'a str,
+    word_count: usize,
+    time_limit: u64,
+    wpm: f64,
+    raw_wpm: f64,
+    accuracy: f64,
+    consistency: f64,
+    errors: usize,
+    corrected_errors: usize,
+    words_typed: usize,
+    duration_secs: f64,
+    quote_id: Option<usize>,
+    source: Option<&'a str>,
+    symbol_accuracy: Option<f64>,
+    imported_from: Option<&'a str>,
+    seed: Option<u64>,
+}
+
+impl<'a> ExportRow<'a> {
+    fn new(record: &'a RunRecord) -> Self {
+        Self {
+            timestamp: record.timestamp,
+            date: history::format_timestamp(record.timestamp),
+            mode: record.mode.label(),
+            language: &record.language,
+            word_count: record.word_count,
+            time_limit: record.time_limit,
+            wpm: record.wpm,
+            raw_wpm: record.raw_wpm,
+            accuracy: record.accuracy,
+            consistency: record.consistency,
+            errors: record.errors,
+            corrected_errors: record.corrected_errors,
+            words_typed: record.words_typed,
+            duration_secs: record.duration_secs,
+            quote_id: record.quote_id,
+            source: record.source.as_deref(),
+            symbol_accuracy: record.symbol_accuracy,
+            imported_from: record.imported_from.as_deref(),
+            seed: record.seed,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct JsonExport<'a> {
+    schema_version: u32,
+    runs: Vec<ExportRow<'a>>,
+}
+
+/// Serialises runs as export rows, leaving out keystroke logs and test text.
+pub fn serialize_rows<S: Serializer>(records: &[RunRecord], serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(records.iter().map(ExportRow::new))
+}
+
+/// Renders the runs in `format`, oldest first.
+pub fn render(records: &[RunRecord], format: ExportFormat) -> Result<String, Box<dyn std::error::Error>> {
+    let rows: Vec<ExportRow> = records.iter().map(ExportRow::new).collect();
+    match format {
+        ExportFormat::Json => {
+            let export = JsonExport { schema_version: SCHEMA_VERSION, runs: rows };
+            Ok(serde_json::to_string_pretty(&export)? + "\n")
+        }
+        ExportFormat::Csv => {
+            let mut out = COLUMNS.join(",") + "\n";
+            for row in rows {
+                let Value::Object(fields) = serde_json::to_value(&row)? else {
+                    unreachable!("export rows serialize to objects");
+                };
+                let cells: Vec<String> = COLUMNS.iter().map(|c| csv_cell(&fields[*c])).collect();
+                out += &cells.join(",");
+                out.push('\n');
+            }
+            Ok(out)
+        }
+    }
+}
+
+fn csv_cell(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) if s.contains([',', '"', '\n', '\r']) => {
+            format!("\"{}\"", s.replace('"', "\"\""))
+        }
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Writes the runs to a new timestamped file in the exports directory and
+/// returns its path.
+pub fn export_to_data_dir(records: &[RunRecord], format: ExportFormat) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let dir = paths::exports_dir().ok_or("Could not determine data directory")?;
+    fs::create_dir_all(&dir)?;
+    let now = history::now_timestamp();
+    let stamp = history::format_timestamp(now).replace([' ', ':'], "-");
+    let path = dir.join(format!("history-{}-{:02}.{}", stamp, now % 60, format.extension()));
+    fs::write(&path, render(records, format)?)?;
+    Ok(path)
+}
@@ -0,0 +1,47 @@
+use super::history::{self, RunRecord};
+use super::replay::Replay;
+
+/// A second caret that races through the test at the pace of an earlier run.
+pub struct Ghost {
+    /// Who the ghost is, e.g. "personal best".
+    pub label: String,
+    /// Net WPM the ghost's run finished with.
+    pub wpm: f64,
+    /// Characters of the test text the ghost has covered so far.
+    pub position: usize,
+    pace: Pace,
+}
+
+enum Pace {
+    /// Replays the run's own keystrokes, so the ghost speeds up and slows
+    /// down where the run did.
+    Recorded(Box<Replay>),
+    /// Runs without a keystroke log move at their average speed.
+    Steady { chars_per_ms: f64 },
+}
+
+impl Ghost {
+    pub fn new(record: &RunRecord, label: String) -> Self {
+        let pace = match Replay::new(record) {
+            Some(replay) => Pace::Recorded(Box::new(replay)),
+            None => Pace::Steady { chars_per_ms: record.wpm * 5.0 / 60_000.0 },
+        };
+        Self { label, wpm: record.wpm, position: 0, pace }
+    }
+
+    /// A ghost of a past run, labelled with when it was typed.
+    pub fn of_run(record: &RunRecord) -> Self {
+        Self::new(record, format!("run of {}", history::format_timestamp(record.timestamp)))
+    }
+
+    /// Moves the ghost to where its run was `elapsed_ms` into the test.
+    pub fn advance(&mut self, elapsed_ms: f64) {
+        self.position = match &mut self.pace {
+            Pace::Recorded(replay) => {
+                replay.seek(elapsed_ms);
+                replay.engine.pos()
+            }
+            Pace::Steady { chars_per_ms } => (elapsed_ms * *chars_per_ms) as usize,
+        };
+    }
+}
@@ -0,0 +1,253 @@
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::time::{SystemTime, UNIX_EPOCH};
+use serde::{Deserialize, Serialize};
+use super::keystrokes::Keystroke;
+use super::paths;
+use super::state::TestMode;
+
+/// One completed test, stored as a single line of the history file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunRecord {
+    /// Seconds since the Unix epoch at which the test finished.
+    pub timestamp: u64,
+    pub mode: TestMode,
+    pub language: String,
+    pub word_count: usize,
+    pub time_limit: u64,
+    pub wpm: f64,
+    #[serde(default)]
+    pub raw_wpm: f64,
+    pub accuracy: f64,
+    #[serde(default)]
+    pub consistency: f64,
+    /// Errors still present when the test ended.
+    pub errors: usize,
+    /// Errors that were typed and then fixed.
+    #[serde(default)]
+    pub corrected_errors: usize,
+    pub words_typed: usize,
+    pub duration_secs: f64,
+    /// The quote typed, for quote-mode runs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quote_id: Option<usize>,
+    /// Where the text came from, for custom and code runs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    /// Accuracy on symbol characters, when the text had any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub symbol_accuracy: Option<f64>,
+    /// The file this run was imported from, for runs typed in another tool.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub imported_from: Option<String>,
+    /// Every key pressed during the test, for per-key statistics and replays.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub keystrokes: Vec<Keystroke>,
+    /// The text the test asked for, so the run can be replayed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    /// Seed the words were generated from, for generated modes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+}
+
+pub fn now_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Reads every run from the history file, oldest first.
+///
+/// A missing file is an empty history. Lines that fail to parse are skipped
+/// and reported through the returned warning.
+pub fn load_history() -> (Vec<RunRecord>, Option<String>) {
+    let Some(path) = paths::history_file() else {
+        return (Vec::new(), None);
+    };
+    let contents = match fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return (Vec::new(), None),
+        Err(err) => {
+            return (Vec::new(), Some(format!("Could not read {}: {}", path.display(), err)));
+        }
+    };
+
+    let mut records = Vec::new();
+    let mut skipped = 0;
+    for line in contents.lines().filter(|l| !l.trim().is_empty()) {
+        match serde_json::from_str::<RunRecord>(line) {
+            Ok(record) => records.push(record),
+            Err(_) => skipped += 1,
+        }
+    }
+
+    let warning = (skipped > 0).then(|| {
+        format!("Skipped {} unreadable line(s) in {}", skipped, path.display())
+    });
+    (records, warning)
+}
+
+pub fn append_run(record: &RunRecord) -> Result<(), Box<dyn std::error::Error>> {
+    append_runs(std::slice::from_ref(record))
+}
+
+pub fn append_runs(records: &[RunRecord]) -> Result<(), Box<dyn std::error::Error>> {
+    let path = paths::history_file().ok_or("Could not determine data directory")?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+    for record in records {
+        writeln!(file, "{}", serde_json::to_string(record)?)?;
+    }
+    Ok(())
+}
+
+/// Formats a Unix timestamp as a UTC "YYYY-MM-DD HH:MM" string.
+pub fn format_timestamp(timestamp: u64) -> String {
+    let (year, month, day) = civil_from_days((timestamp / 86_400) as i64);
+    let secs = timestamp % 86_400;
+    format!("{:04}-{:02}-{:02} {:02}:{:02}", year, month, day, secs / 3600, secs % 3600 / 60)
+}
+
+/// Parses a Unix timestamp in seconds or milliseconds, or a date such as
+/// "2024-03-01", "2024-03-01 14:05" or "2024-03-01T14:05:09.123Z". Dates
+/// are UTC unless they end in an offset like "+05:00". Anything out of range
+/// gives `None`.
+pub fn parse_timestamp(text: &str) -> Option<u64> {
+    if let Ok(number) = text.parse::<u64>() {
+        // Anything this large is in milliseconds (seconds would be past year 5000)
+        return Some(if number >= 100_000_000_000 { number / 1000 } else { number });
+    }
+
+    let (date, time) = match text.split_once(['T', ' ']) {
+        Some((date, time)) => (date, time),
+        None => (text, ""),
+    };
+    let mut date_parts = date.split('-').map(|p| p.parse::<i64>().ok());
+    let (year, month, day) = (date_parts.next()??, date_parts.next()??, date_parts.next()??);
+    if date_parts.next().is_some()
+        || !(0..=9999).contains(&year)
+        || !(1..=12).contains(&month)
+        || !(1..=31).contains(&day)
+    {
+        return None;
+    }
+
+    let (clock, offset) = match time.strip_suffix('Z') {
+        Some(clock) => (clock, 0),
+        None => match time.find(['+', '-']) {
+            Some(i) => (&time[..i], parse_offset(&time[i..])?),
+            None => (time, 0),
+        },
+    };
+    let clock = clock.split('.').next().unwrap_or("");
+    let parts: Vec<&str> = clock.split(':').filter(|p| !p.is_empty()).collect();
+    if parts.len() > 3 {
+        return None;
+    }
+    let mut seconds: i64 = 0;
+    for (part, (scale, limit)) in parts.into_iter().zip([(3600, 24), (60, 60), (1, 61)]) {
+        let value = part.parse::<i64>().ok().filter(|v| (0..limit).contains(v))?;
+        seconds = seconds.checked_add(value.checked_mul(scale)?)?;
+    }
+
+    let days = days_from_civil(year, month as u32, day as u32);
+    let timestamp = days.checked_mul(86_400)?.checked_add(seconds)?.checked_sub(offset)?;
+    u64::try_from(timestamp).ok()
+}
+
+/// Parses a UTC offset such as "+05:00", "-0330" or "+01" into seconds.
+fn parse_offset(text: &str) -> Option<i64> {
+    let (sign, rest) = match (text.strip_prefix('+'), text.strip_prefix('-')) {
+        (Some(rest), _) => (1, rest),
+        (_, Some(rest)) => (-1, rest),
+        _ => return None,
+    };
+    let digits = rest.replace(':', "");
+    if !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let (hours, minutes) = match digits.len() {
+        2 => (digits.as_str(), "0"),
+        4 => digits.split_at(2),
+        _ => return None,
+    };
+    let (hours, minutes) = (hours.parse::<i64>().ok()?, minutes.parse::<i64>().ok()?);
+    if hours > 23 || minutes > 59 {
+        return None;
+    }
+    Some(sign * (hours * 3600 + minutes * 60))
+}
+
+/// Converts a civil date to days since 1970-01-01.
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    // Howard Hinnant's days-from-civil algorithm
+    let year = year - i64::from(month <= 2);
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let mp = i64::from((month + 9) % 12);
+    let doy = (153 * mp + 2) / 5 + i64::from(day) - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Converts days since 1970-01-01 to a (year, month, day) civil date.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    // Howard Hinnant's days-to-civil algorithm
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_unix_seconds_and_milliseconds() {
+        assert_eq!(parse_timestamp("1709301909"), Some(1_709_301_909));
+        assert_eq!(parse_timestamp("1709301909123"), Some(1_709_301_909));
+    }
+
+    #[test]
+    fn parses_dates_as_utc() {
+        assert_eq!(parse_timestamp("2024-03-01"), Some(1_709_251_200));
+        assert_eq!(parse_timestamp("2024-03-01 14:05"), Some(1_709_301_900));
+        assert_eq!(parse_timestamp("2024-03-01T14:05:09.123Z"), Some(1_709_301_909));
+    }
+
+    #[test]
+    fn applies_utc_offsets() {
+        assert_eq!(parse_timestamp("2024-03-01T14:05:09+05:00"), Some(1_709_301_909 - 5 * 3600));
+        assert_eq!(parse_timestamp("2024-03-01T14:05:09-0530"), Some(1_709_301_909 + 5 * 3600 + 1800));
+        assert_eq!(parse_timestamp("2024-03-01T14:05:09+01"), Some(1_709_301_909 - 3600));
+        assert_eq!(parse_timestamp("2024-03-01T14:05:09+5"), None);
+    }
+
+    #[test]
+    fn rejects_out_of_range_values() {
+        assert_eq!(parse_timestamp("99999999999999-01-01"), None);
+        assert_eq!(parse_timestamp("2024-13-01"), None);
+        assert_eq!(parse_timestamp("2024-03-01 25:00"), None);
+        assert_eq!(parse_timestamp("2024-03-01 14:05:09:01"), None);
+        assert_eq!(parse_timestamp("2024-03-01 99999999999999999:00"), None);
+        assert_eq!(parse_timestamp("1969-12-31"), None);
+        assert_eq!(parse_timestamp("yesterday"), None);
+    }
+
+    #[test]
+    fn formats_what_it_parses() {
+        let timestamp = parse_timestamp("2024-03-01 14:05").unwrap();
+        assert_eq!(format_timestamp(timestamp), "2024-03-01 14:05");
+    }
+}
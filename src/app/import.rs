@@ -0,0 +1,269 @@
+use std::collections::HashSet;
+use super::history::{self, RunRecord};
+use super::state::TestMode;
+
+/// What an import added, and what it left out.
+#[derive(Debug, Default)]
+pub struct ImportReport {
+    pub imported: Vec<RunRecord>,
+    /// Rows matching a run already in the history (or earlier in the file).
+    pub duplicates: usize,
+    /// Rows missing a usable wpm, accuracy or timestamp.
+    pub invalid: usize,
+}
+
+/// Header names accepted for each field, compared after lowercasing and
+/// dropping everything but letters and digits, so `rawWpm` matches `raw_wpm`.
+const TIMESTAMP: &[&str] = &["timestamp", "date", "datetime", "time"];
+const WPM: &[&str] = &["wpm", "netwpm"];
+const ACCURACY: &[&str] = &["acc", "accuracy"];
+const RAW_WPM: &[&str] = &["rawwpm", "raw", "grosswpm"];
+const CONSISTENCY: &[&str] = &["consistency"];
+const MODE: &[&str] = &["mode"];
+/// Monkeytype's mode detail: the word count or time limit.
+const MODE2: &[&str] = &["mode2"];
+const LANGUAGE: &[&str] = &["language", "lang"];
+const DURATION: &[&str] = &["testduration", "durationsecs", "duration"];
+const WORD_COUNT: &[&str] = &["wordcount"];
+const TIME_LIMIT: &[&str] = &["timelimit"];
+const ERRORS: &[&str] = &["errors"];
+const CORRECTED: &[&str] = &["correctederrors"];
+const WORDS_TYPED: &[&str] = &["wordstyped"];
+/// Monkeytype's "correct;incorrect;extra;missed" character counts.
+const CHAR_STATS: &[&str] = &["charstats"];
+
+/// Parses a CSV export from remytype or another typing test, skipping runs
+/// that are already in `existing`. `origin` is stored on every imported run.
+pub fn import_csv(text: &str, origin: &str, existing: &[RunRecord]) -> Result<ImportReport, String> {
+    let mut rows = parse_csv(text).into_iter();
+    let header: Vec<String> = rows.next().ok_or("the file is empty")?.iter().map(|h| normalize(h)).collect();
+    let column = |names: &[&str]| header.iter().position(|h| names.contains(&h.as_str()));
+
+    let required = [("timestamp", TIMESTAMP), ("wpm", WPM), ("accuracy", ACCURACY)];
+    for (name, names) in required {
+        if column(names).is_none() {
+            return Err(format!("no {} column (expected one of: {})", name, names.join(", ")));
+        }
+    }
+
+    let columns = Columns {
+        timestamp: column(TIMESTAMP),
+        wpm: column(WPM),
+        accuracy: column(ACCURACY),
+        raw_wpm: column(RAW_WPM),
+        consistency: column(CONSISTENCY),
+        mode: column(MODE),
+        mode2: column(MODE2),
+        language: column(LANGUAGE),
+        duration: column(DURATION),
+        word_count: column(WORD_COUNT),
+        time_limit: column(TIME_LIMIT),
+        errors: column(ERRORS),
+        corrected: column(CORRECTED),
+        words_typed: column(WORDS_TYPED),
+        char_stats: column(CHAR_STATS),
+    };
+
+    let mut seen: HashSet<(u64, i64)> = existing.iter().map(dedupe_key).collect();
+    let mut report = ImportReport::default();
+    for row in rows.filter(|r| r.iter().any(|cell| !cell.is_empty())) {
+        match columns.record(&row, origin) {
+            Some(record) if seen.insert(dedupe_key(&record)) => report.imported.push(record),
+            Some(_) => report.duplicates += 1,
+            None => report.invalid += 1,
+        }
+    }
+    report.imported.sort_by_key(|r| r.timestamp);
+    Ok(report)
+}
+
+/// Runs are the same if they finished in the same second at the same speed.
+fn dedupe_key(record: &RunRecord) -> (u64, i64) {
+    (record.timestamp, (record.wpm * 100.0).round() as i64)
+}
+
+fn normalize(header: &str) -> String {
+    header
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+struct Columns {
+    timestamp: Option<usize>,
+    wpm: Option<usize>,
+    accuracy: Option<usize>,
+    raw_wpm: Option<usize>,
+    consistency: Option<usize>,
+    mode: Option<usize>,
+    mode2: Option<usize>,
+    language: Option<usize>,
+    duration: Option<usize>,
+    word_count: Option<usize>,
+    time_limit: Option<usize>,
+    errors: Option<usize>,
+    corrected: Option<usize>,
+    words_typed: Option<usize>,
+    char_stats: Option<usize>,
+}
+
+impl Columns {
+    fn record(&self, row: &[String], origin: &str) -> Option<RunRecord> {
+        let cell = |column: Option<usize>| {
+            column
+                .and_then(|i| row.get(i))
+                .map(|s| s.trim())
+                .filter(|s| !s.is_empty())
+        };
+        let number = |column: Option<usize>| cell(column).and_then(|s| s.parse::<f64>().ok());
+
+        let timestamp = history::parse_timestamp(cell(self.timestamp)?)?;
+        let wpm = number(self.wpm)?;
+        let mut accuracy = number(self.accuracy)?;
+        // Some tools store accuracy as a fraction rather than a percentage
+        if accuracy <= 1.0 {
+            accuracy *= 100.0;
+        }
+
+        let mode = match cell(self.mode) {
+            Some(mode) => TestMode::ALL
+                .into_iter()
+                .find(|m| m.label() == mode.to_lowercase())
+                .unwrap_or(TestMode::Custom),
+            None => TestMode::Custom,
+        };
+        let mode2 = number(self.mode2);
+        let word_count = number(self.word_count)
+            .or(mode2.filter(|_| mode == TestMode::Words))
+            .unwrap_or(0.0) as usize;
+        let time_limit = number(self.time_limit)
+            .or(mode2.filter(|_| mode == TestMode::Time))
+            .unwrap_or(0.0) as u64;
+        let duration_secs = number(self.duration).unwrap_or(time_limit as f64);
+
+        // "correct;incorrect;extra;missed"
+        let char_stats: Vec<usize> = cell(self.char_stats)
+            .map(|s| s.split(';').filter_map(|n| n.trim().parse().ok()).collect())
+            .unwrap_or_default();
+        let errors = number(self.errors)
+            .map(|n| n as usize)
+            .unwrap_or_else(|| char_stats.iter().skip(1).sum());
+        let words_typed = number(self.words_typed)
+            .map(|n| n as usize)
+            .unwrap_or_else(|| (wpm * duration_secs / 60.0).round() as usize);
+
+        Some(RunRecord {
+            timestamp,
+            mode,
+            language: cell(self.language).unwrap_or("unknown").to_string(),
+            word_count,
+            time_limit,
+            wpm,
+            raw_wpm: number(self.raw_wpm).unwrap_or(wpm),
+            accuracy,
+            consistency: number(self.consistency).unwrap_or(0.0),
+            errors,
+            corrected_errors: number(self.corrected).unwrap_or(0.0) as usize,
+            words_typed,
+            duration_secs,
+            quote_id: None,
+            source: None,
+            symbol_accuracy: None,
+            imported_from: Some(origin.to_string()),
+            keystrokes: Vec::new(),
+            text: None,
+            seed: None,
+        })
+    }
+}
+
+/// Splits CSV text into rows of cells, handling quoted cells with embedded
+/// commas, quotes and line breaks.
+fn parse_csv(text: &str) -> Vec<Vec<String>> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut cell = String::new();
+    let mut in_quotes = false;
+    let mut chars = text.trim_start_matches('\u{feff}').chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                cell.push('"');
+                chars.next();
+            }
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => row.push(std::mem::take(&mut cell)),
+            '\r' if !in_quotes => {}
+            '\n' if !in_quotes => {
+                row.push(std::mem::take(&mut cell));
+                rows.push(std::mem::take(&mut row));
+            }
+            _ => cell.push(c),
+        }
+    }
+    if !cell.is_empty() || !row.is_empty() {
+        row.push(cell);
+        rows.push(row);
+    }
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csv_handles_quotes_and_line_endings() {
+        let rows = parse_csv("\u{feff}a,\"b, c\",\"say \"\"hi\"\"\"\r\n1,\"two\nlines\",3\n");
+        assert_eq!(rows, [vec!["a", "b, c", "say \"hi\""], vec!["1", "two\nlines", "3"]]);
+    }
+
+    #[test]
+    fn csv_keeps_a_last_row_without_newline() {
+        assert_eq!(parse_csv("a,b\n1,"), [vec!["a", "b"], vec!["1", ""]]);
+    }
+
+    const MONKEYTYPE: &str = "\
+_id,wpm,rawWpm,acc,consistency,mode,mode2,language,testDuration,charStats,timestamp
+1,95.2,98.1,97.3,80,time,30,english,30,480;5;2;1,1709301909000
+2,80,82,0.95,75,words,25,english,18.5,300;10;0;0,1709300000000
+3,not a number,82,95,75,words,25,english,18.5,,1709300001000
+4,70,72,95,75,zen,,english,20,,99999999999999-01-01
+";
+
+    #[test]
+    fn imports_monkeytype_rows() {
+        let report = import_csv(MONKEYTYPE, "results.csv", &[]).unwrap();
+        assert_eq!(report.imported.len(), 2);
+        assert_eq!(report.invalid, 2);
+
+        // Sorted oldest first
+        let words = &report.imported[0];
+        assert_eq!(words.mode, TestMode::Words);
+        assert_eq!(words.word_count, 25);
+        assert_eq!(words.accuracy, 95.0);
+        let time = &report.imported[1];
+        assert_eq!(time.timestamp, 1_709_301_909);
+        assert_eq!(time.mode, TestMode::Time);
+        assert_eq!(time.time_limit, 30);
+        assert_eq!(time.errors, 8);
+        assert_eq!(time.imported_from.as_deref(), Some("results.csv"));
+    }
+
+    #[test]
+    fn skips_runs_already_imported() {
+        let first = import_csv(MONKEYTYPE, "results.csv", &[]).unwrap();
+        let again = import_csv(MONKEYTYPE, "results.csv", &first.imported).unwrap();
+        assert!(again.imported.is_empty());
+        assert_eq!(again.duplicates, 2);
+    }
+
+    #[test]
+    fn requires_the_key_columns() {
+        let err = import_csv("timestamp,wpm\n1709301909,90\n", "x.csv", &[]).unwrap_err();
+        assert!(err.starts_with("no accuracy column"));
+        assert!(import_csv("", "x.csv", &[]).is_err());
+    }
+}
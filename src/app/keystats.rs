@@ -0,0 +1,148 @@
+use std::collections::HashMap;
+use super::history::RunRecord;
+use super::keystrokes::{KeyAction, Keystroke};
+
+/// Gaps longer than this are pauses rather than typing, and are left out of
+/// latency averages.
+pub const MAX_LATENCY_MS: u64 = 2000;
+
+/// Keys and n-grams typed fewer times than this are too noisy to rank.
+pub const MIN_ATTEMPTS: usize = 5;
+
+/// How often one key was typed correctly, and how quickly.
+#[derive(Debug, Clone, Default)]
+pub struct KeyStats {
+    /// Times the key was due to be typed.
+    pub attempts: usize,
+    /// Attempts where a different key was pressed.
+    pub errors: usize,
+    /// Sum of the gaps before each timed attempt.
+    pub total_latency_ms: u64,
+    /// Attempts with a latency, i.e. not the first key or after a pause.
+    pub timed: usize,
+}
+
+impl KeyStats {
+    /// Percentage of attempts that were wrong.
+    pub fn error_rate(&self) -> f64 {
+        if self.attempts == 0 {
+            return 0.0;
+        }
+        self.errors as f64 / self.attempts as f64 * 100.0
+    }
+
+    /// Average milliseconds between the previous key and this one.
+    pub fn average_latency(&self) -> Option<f64> {
+        (self.timed > 0).then(|| self.total_latency_ms as f64 / self.timed as f64)
+    }
+}
+
+/// Per-key statistics across every run with a keystroke log, keyed by the
+/// physical key (see `key_of`).
+pub fn per_key(records: &[RunRecord]) -> HashMap<char, KeyStats> {
+    let mut stats: HashMap<char, KeyStats> = HashMap::new();
+    for record in records {
+        add_run(&mut stats, &record.keystrokes);
+    }
+    stats
+}
+
+fn add_run(stats: &mut HashMap<char, KeyStats>, keystrokes: &[Keystroke]) {
+    let mut previous_at = None;
+    for keystroke in keystrokes {
+        if let KeyAction::Char { expected: Some(expected), correct, .. } = keystroke.action
+            && let Some(key) = key_of(expected)
+        {
+            let entry = stats.entry(key).or_default();
+            entry.attempts += 1;
+            if !correct {
+                entry.errors += 1;
+            }
+            if let Some(previous) = previous_at {
+                let latency = keystroke.at_ms.saturating_sub(previous);
+                if latency <= MAX_LATENCY_MS {
+                    entry.total_latency_ms += latency;
+                    entry.timed += 1;
+                }
+            }
+        }
+        previous_at = Some(keystroke.at_ms);
+    }
+}
+
+/// Per n-gram statistics across every run with a keystroke log, keyed by the
+/// lowercased characters. An attempt is `n` keys typed in a row without a
+/// backspace, all correct except possibly the last, so it measures the
+/// transition into the final key. Its latency runs from the first key to the
+/// last. N-grams spanning whitespace are left out.
+pub fn per_ngram(records: &[RunRecord], n: usize) -> HashMap<String, KeyStats> {
+    let mut stats: HashMap<String, KeyStats> = HashMap::new();
+    for record in records {
+        add_ngrams(&mut stats, &record.keystrokes, n);
+    }
+    stats
+}
+
+/// Statistics for one n-gram in each run that contains it, oldest first.
+pub fn ngram_trend(records: &[RunRecord], ngram: &str) -> Vec<(u64, KeyStats)> {
+    let n = ngram.chars().count();
+    let mut trend: Vec<(u64, KeyStats)> = records
+        .iter()
+        .filter_map(|record| {
+            let mut stats = HashMap::new();
+            add_ngrams(&mut stats, &record.keystrokes, n);
+            stats.remove(ngram).map(|s| (record.timestamp, s))
+        })
+        .collect();
+    trend.sort_by_key(|(timestamp, _)| *timestamp);
+    trend
+}
+
+fn add_ngrams(stats: &mut HashMap<String, KeyStats>, keystrokes: &[Keystroke], n: usize) {
+    // (expected character, time, correct) for the last `n` keys
+    let mut window: Vec<(char, u64, bool)> = Vec::with_capacity(n + 1);
+    for keystroke in keystrokes {
+        let KeyAction::Char { expected: Some(expected), correct, .. } = keystroke.action else {
+            window.clear();
+            continue;
+        };
+        if expected.is_whitespace() {
+            window.clear();
+            continue;
+        }
+        window.push((expected.to_lowercase().next().unwrap_or(expected), keystroke.at_ms, correct));
+        if window.len() > n {
+            window.remove(0);
+        }
+        if window.len() < n || !window[..n - 1].iter().all(|&(_, _, correct)| correct) {
+            continue;
+        }
+
+        let ngram: String = window.iter().map(|&(c, _, _)| c).collect();
+        let entry = stats.entry(ngram).or_default();
+        entry.attempts += 1;
+        if !correct {
+            entry.errors += 1;
+        }
+        let latency = window[n - 1].1.saturating_sub(window[0].1);
+        if n > 1 && latency <= MAX_LATENCY_MS * (n as u64 - 1) {
+            entry.total_latency_ms += latency;
+            entry.timed += 1;
+        }
+    }
+}
+
+const SHIFTED: &str = "~!@#$%^&*()_+{}|:\"<>?";
+const UNSHIFTED: &str = "`1234567890-=[]\\;',./";
+
+/// The key on a US QWERTY keyboard that types `c`, as its unshifted
+/// character. Space and newline map to themselves.
+pub fn key_of(c: char) -> Option<char> {
+    if c.is_ascii_alphabetic() {
+        return Some(c.to_ascii_lowercase());
+    }
+    if let Some(i) = SHIFTED.find(c) {
+        return UNSHIFTED[i..].chars().next();
+    }
+    (c.is_ascii_graphic() || c == ' ' || c == '\n').then_some(c)
+}
@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum KeyAction {
+    Char {
+        typed: char,
+        /// The character under the caret when the key was pressed, if any.
+        expected: Option<char>,
+        correct: bool,
+    },
+    Backspace,
+}
+
+/// A single key press during a test.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Keystroke {
+    /// Milliseconds since the test started.
+    pub at_ms: u64,
+    pub action: KeyAction,
+}
@@ -0,0 +1,132 @@
+use super::keystrokes::{KeyAction, Keystroke};
+
+/// Characters per word in every WPM figure.
+const CHARS_PER_WORD: f64 = 5.0;
+
+/// Typing speed during one second of a test.
+#[derive(Debug, Clone)]
+pub struct SecondSample {
+    /// 1-based second this sample covers (`second - 1`..`second`).
+    pub second: u64,
+    /// Net WPM from the start of the test up to the end of this second.
+    pub wpm: f64,
+    /// Raw WPM from the keys pressed during this second alone.
+    pub raw_wpm: f64,
+    pub errors: usize,
+}
+
+/// Headline figures for a finished test.
+#[derive(Debug, Clone, Default)]
+pub struct TestMetrics {
+    /// Correctly typed characters per minute, divided by five.
+    pub wpm: f64,
+    /// Every typed character per minute, divided by five.
+    pub raw_wpm: f64,
+    /// Share of character keystrokes that were correct, including ones later corrected.
+    pub accuracy: f64,
+    /// 100 minus the coefficient of variation of per-second raw speed, as a percentage.
+    pub consistency: f64,
+}
+
+/// Computes the headline metrics from the keystroke log and the number of
+/// characters that ended up correct in the final input.
+pub fn summarize(keystrokes: &[Keystroke], correct_chars: usize, duration_secs: f64) -> TestMetrics {
+    let typed = keystrokes
+        .iter()
+        .filter(|k| matches!(k.action, KeyAction::Char { .. }))
+        .count();
+    let correct_keystrokes = keystrokes
+        .iter()
+        .filter(|k| matches!(k.action, KeyAction::Char { correct: true, .. }))
+        .count();
+
+    let accuracy = if typed > 0 {
+        correct_keystrokes as f64 / typed as f64 * 100.0
+    } else {
+        0.0
+    };
+
+    TestMetrics {
+        wpm: wpm(correct_chars, duration_secs),
+        raw_wpm: wpm(typed, duration_secs),
+        accuracy,
+        consistency: consistency(&timeline(keystrokes, duration_secs)),
+    }
+}
+
+/// Accuracy on symbol characters (anything not a letter, digit or
+/// whitespace), or `None` when the text had no symbols to type.
+pub fn symbol_accuracy(keystrokes: &[Keystroke]) -> Option<f64> {
+    let mut total = 0;
+    let mut correct = 0;
+    for stroke in keystrokes {
+        if let KeyAction::Char { expected: Some(expected), correct: was_correct, .. } = stroke.action
+            && !expected.is_alphanumeric()
+            && !expected.is_whitespace()
+        {
+            total += 1;
+            if was_correct {
+                correct += 1;
+            }
+        }
+    }
+    (total > 0).then(|| correct as f64 / total as f64 * 100.0)
+}
+
+fn consistency(samples: &[SecondSample]) -> f64 {
+    if samples.is_empty() {
+        return 0.0;
+    }
+    let count = samples.len() as f64;
+    let mean = samples.iter().map(|s| s.raw_wpm).sum::<f64>() / count;
+    if mean <= 0.0 {
+        return 0.0;
+    }
+    let variance = samples.iter().map(|s| (s.raw_wpm - mean).powi(2)).sum::<f64>() / count;
+    let cv = variance.sqrt() / mean;
+    ((1.0 - cv) * 100.0).clamp(0.0, 100.0)
+}
+
+/// Splits the keystroke log of a test lasting `duration_secs` into per-second samples.
+pub fn timeline(keystrokes: &[Keystroke], duration_secs: f64) -> Vec<SecondSample> {
+    let seconds = duration_secs.ceil() as u64;
+    let mut samples = Vec::with_capacity(seconds as usize);
+    let mut correct_so_far = 0;
+
+    for second in 1..=seconds {
+        let from_ms = (second - 1) * 1000;
+        let to_ms = second * 1000;
+        let mut typed = 0;
+        let mut errors = 0;
+        let in_window = |k: &&Keystroke| k.at_ms >= from_ms && (k.at_ms < to_ms || second == seconds);
+        for stroke in keystrokes.iter().filter(in_window) {
+            if let KeyAction::Char { correct, .. } = stroke.action {
+                typed += 1;
+                if correct {
+                    correct_so_far += 1;
+                } else {
+                    errors += 1;
+                }
+            }
+        }
+
+        // The final second is usually partial, so scale by its real length
+        let window = (duration_secs - (second - 1) as f64).clamp(0.001, 1.0);
+        let elapsed = duration_secs.min(second as f64);
+        samples.push(SecondSample {
+            second,
+            wpm: wpm(correct_so_far, elapsed),
+            raw_wpm: wpm(typed, window),
+            errors,
+        });
+    }
+
+    samples
+}
+
+fn wpm(chars: usize, seconds: f64) -> f64 {
+    if seconds <= 0.0 {
+        return 0.0;
+    }
+    chars as f64 / CHARS_PER_WORD / (seconds / 60.0)
+}
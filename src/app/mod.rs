@@ -1,3 +1,17 @@
+pub mod config;
+pub mod custom;
+pub mod engine;
+pub mod export;
+pub mod ghost;
+pub mod history;
+pub mod keystats;
+pub mod import;
+pub mod keystrokes;
+pub mod metrics;
+pub mod paths;
+pub mod practice;
+pub mod replay;
 pub mod state;
+pub mod summary;
 
 pub use state::{App, AppMode, TestMode};
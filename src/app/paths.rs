@@ -0,0 +1,50 @@
+use std::env;
+use std::path::PathBuf;
+
+const APP_DIR: &str = "remytype";
+
+/// Per-user config directory, following the XDG base directory spec.
+pub fn config_dir() -> Option<PathBuf> {
+    xdg_dir("XDG_CONFIG_HOME", ".config")
+}
+
+/// Per-user data directory, following the XDG base directory spec.
+pub fn data_dir() -> Option<PathBuf> {
+    xdg_dir("XDG_DATA_HOME", ".local/share")
+}
+
+pub fn settings_file() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join("settings.toml"))
+}
+
+pub fn history_file() -> Option<PathBuf> {
+    data_dir().map(|dir| dir.join("history.jsonl"))
+}
+
+/// Directory scanned for user-supplied source files for code mode.
+pub fn code_dir() -> Option<PathBuf> {
+    data_dir().map(|dir| dir.join("code"))
+}
+
+/// Directory scanned for user-supplied `*.json` word lists.
+pub fn languages_dir() -> Option<PathBuf> {
+    data_dir().map(|dir| dir.join("languages"))
+}
+
+/// Directory scanned for user-supplied `*.toml` themes.
+pub fn themes_dir() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join("themes"))
+}
+
+/// Where exports made from the Stats screen are written.
+pub fn exports_dir() -> Option<PathBuf> {
+    data_dir().map(|dir| dir.join("exports"))
+}
+
+fn xdg_dir(var: &str, home_fallback: &str) -> Option<PathBuf> {
+    let base = match env::var_os(var) {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => PathBuf::from(env::var_os("HOME")?).join(home_fallback),
+    };
+    Some(base.join(APP_DIR))
+}
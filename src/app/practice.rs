@@ -0,0 +1,89 @@
+use std::collections::HashMap;
+use super::history::RunRecord;
+use super::keystats::{self, KeyStats, MIN_ATTEMPTS};
+
+/// How many recent runs practice learns from, so it follows improvement.
+const RECENT_RUNS: usize = 50;
+/// How many of the weakest characters and bigrams are targeted at once.
+const TARGETS: usize = 6;
+/// Extra weight a word gets per unit of weakness it contains.
+const BOOST: f64 = 3.0;
+
+/// The characters and bigrams a user is slowest or least accurate on.
+#[derive(Debug, Clone, Default)]
+pub struct WeakSpots {
+    /// Target (one or two lowercase characters) and how far it is below the
+    /// user's average, where 1.0 means twice as bad.
+    targets: Vec<(String, f64)>,
+}
+
+impl WeakSpots {
+    pub fn from_history(records: &[RunRecord]) -> Self {
+        let recent = &records[records.len().saturating_sub(RECENT_RUNS)..];
+        let chars = keystats::per_key(recent)
+            .into_iter()
+            .filter(|(c, _)| c.is_alphanumeric())
+            .map(|(c, stats)| (c.to_string(), stats))
+            .collect();
+        let bigrams = keystats::per_ngram(recent, 2)
+            .into_iter()
+            .filter(|(b, _)| b.chars().all(char::is_alphanumeric))
+            .collect();
+
+        let mut targets = weakness(chars);
+        targets.extend(weakness(bigrams));
+        targets.retain(|(_, score)| *score > 0.0);
+        targets.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+        targets.truncate(TARGETS);
+        Self { targets }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.targets.is_empty()
+    }
+
+    /// The targeted characters and bigrams, weakest first.
+    pub fn targets(&self) -> Vec<String> {
+        self.targets.iter().map(|(t, _)| t.clone()).collect()
+    }
+
+    /// Relative chance of picking `word`: 1.0 plus a boost for every weak
+    /// spot it contains.
+    pub fn word_weight(&self, word: &str) -> f64 {
+        let word = word.to_lowercase();
+        let boost: f64 = self
+            .targets
+            .iter()
+            .map(|(target, score)| word.matches(target.as_str()).count() as f64 * score)
+            .sum();
+        1.0 + BOOST * boost
+    }
+}
+
+/// Scores each entry by how much worse than the group average its error rate
+/// and latency are. Entries at or better than average score zero or less.
+fn weakness(stats: HashMap<String, KeyStats>) -> Vec<(String, f64)> {
+    let judged: Vec<(String, KeyStats)> = stats
+        .into_iter()
+        .filter(|(_, s)| s.attempts >= MIN_ATTEMPTS)
+        .collect();
+    let (attempts, errors, latency, timed) = judged.iter().fold((0, 0, 0, 0), |acc, (_, s)| {
+        (acc.0 + s.attempts, acc.1 + s.errors, acc.2 + s.total_latency_ms, acc.3 + s.timed)
+    });
+    let average_error = if attempts > 0 { errors as f64 / attempts as f64 * 100.0 } else { 0.0 };
+    let average_latency = if timed > 0 { latency as f64 / timed as f64 } else { 0.0 };
+
+    judged
+        .into_iter()
+        .map(|(name, s)| {
+            let mut score = 0.0;
+            if average_error > 0.0 {
+                score += s.error_rate() / average_error - 1.0;
+            }
+            if let Some(latency) = s.average_latency().filter(|_| average_latency > 0.0) {
+                score += latency / average_latency - 1.0;
+            }
+            (name, score)
+        })
+        .collect()
+}
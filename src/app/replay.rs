@@ -0,0 +1,115 @@
+use std::time::Instant;
+use super::engine::TypingEngine;
+use super::history::RunRecord;
+use super::keystrokes::{KeyAction, Keystroke};
+use super::state::AppMode;
+
+/// Playback speeds, slowest first.
+pub const SPEEDS: [f64; 3] = [0.5, 1.0, 2.0];
+
+/// A finished run played back through its own engine, one recorded key at a time.
+pub struct Replay {
+    pub engine: TypingEngine,
+    pub speed: f64,
+    pub paused: bool,
+    /// Playback position, in the run's own milliseconds.
+    pub position_ms: f64,
+    pub duration_ms: f64,
+    /// Net WPM the run finished with.
+    pub wpm: f64,
+    /// Where to go when the replay is closed.
+    pub return_to: AppMode,
+    text: String,
+    keystrokes: Vec<Keystroke>,
+    /// Index of the next keystroke to apply.
+    next: usize,
+    last_tick: Instant,
+}
+
+impl Replay {
+    /// Sets up a replay, or `None` for runs recorded without their text or keys.
+    pub fn new(record: &RunRecord) -> Option<Self> {
+        let text = record.text.clone()?;
+        if record.keystrokes.is_empty() {
+            return None;
+        }
+        Some(Self {
+            engine: TypingEngine::new(&text),
+            speed: 1.0,
+            paused: false,
+            position_ms: 0.0,
+            duration_ms: record.duration_secs * 1000.0,
+            wpm: record.wpm,
+            return_to: AppMode::Menu,
+            text,
+            keystrokes: record.keystrokes.clone(),
+            next: 0,
+            last_tick: Instant::now(),
+        })
+    }
+
+    /// Moves playback on by the real time since the last tick, scaled by the speed.
+    pub fn tick(&mut self) {
+        let now = Instant::now();
+        let mut position = self.position_ms;
+        if !self.paused {
+            position += now.duration_since(self.last_tick).as_secs_f64() * 1000.0 * self.speed;
+        }
+        self.last_tick = now;
+        self.seek(position);
+    }
+
+    /// Plays forward to `position_ms`. Seeking backwards is not supported.
+    pub fn seek(&mut self, position_ms: f64) {
+        self.position_ms = position_ms.min(self.duration_ms);
+        while let Some(keystroke) = self.keystrokes.get(self.next) {
+            if keystroke.at_ms as f64 > self.position_ms {
+                break;
+            }
+            match keystroke.action {
+                KeyAction::Char { typed, .. } => {
+                    self.engine.type_char(typed);
+                }
+                KeyAction::Backspace => {
+                    self.engine.backspace();
+                }
+            }
+            self.next += 1;
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.position_ms >= self.duration_ms
+    }
+
+    /// Net WPM up to the current position.
+    pub fn current_wpm(&self) -> f64 {
+        if self.position_ms <= 0.0 {
+            return 0.0;
+        }
+        self.engine.correct_chars() as f64 / 5.0 / (self.position_ms / 60_000.0)
+    }
+
+    pub fn restart(&mut self) {
+        self.engine = TypingEngine::new(&self.text);
+        self.position_ms = 0.0;
+        self.next = 0;
+        self.paused = false;
+        self.last_tick = Instant::now();
+    }
+
+    pub fn toggle_pause(&mut self) {
+        if self.is_finished() {
+            self.restart();
+        } else {
+            self.paused = !self.paused;
+        }
+    }
+
+    /// Steps to the next faster or slower speed, stopping at either end.
+    pub fn change_speed(&mut self, faster: bool) {
+        let idx = SPEEDS.iter().position(|&s| s == self.speed).unwrap_or(1);
+        let idx = if faster { (idx + 1).min(SPEEDS.len() - 1) } else { idx.saturating_sub(1) };
+        self.speed = SPEEDS[idx];
+    }
+}
@@ -1,5 +1,21 @@
-use std::time::Instant;
-use crate::languages::LanguageManager;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use serde::{Deserialize, Serialize};
+use crate::languages::{LanguageManager, TextOptions};
+use crate::code::{CodeManager, Snippet};
+use crate::quotes::{Quote, QuoteLength, QuoteManager};
+use crate::themes::{Theme, ThemeManager};
+use super::config;
+use super::custom::{CustomOptions, CustomText};
+use super::engine::{CharCounts, TypingEngine};
+use super::export::{self, ExportFormat};
+use super::ghost::Ghost;
+use super::history::{self, RunRecord};
+use super::keystats::{self, KeyStats};
+use super::keystrokes::{KeyAction, Keystroke};
+use super::metrics::{self, SecondSample};
+use super::practice::WeakSpots;
+use super::replay::Replay;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum AppMode {
@@ -8,21 +24,100 @@ pub enum AppMode {
     Results,
     Stats,
     Settings,
+    Diagnostics,
+    Replay,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+/// Pages of the Stats screen, switched with Tab.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StatsView {
+    Overview,
+    Keyboard,
+    Ngrams,
+    Runs,
+}
+
+impl StatsView {
+    pub const ALL: [StatsView; 4] = [
+        StatsView::Overview,
+        StatsView::Keyboard,
+        StatsView::Ngrams,
+        StatsView::Runs,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            StatsView::Overview => "Overview",
+            StatsView::Keyboard => "Keyboard",
+            StatsView::Ngrams => "N-grams",
+            StatsView::Runs => "Runs",
+        }
+    }
+}
+
+/// What the keyboard heatmap colours keys by, and the n-gram list sorts by.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum KeyMetric {
+    ErrorRate,
+    Latency,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum TestMode {
     Words,
     Time,
+    Quote,
+    Custom,
+    Code,
+    Practice,
 }
 
-#[derive(Debug, Clone)]
+impl TestMode {
+    pub const ALL: [TestMode; 6] = [
+        TestMode::Words,
+        TestMode::Time,
+        TestMode::Quote,
+        TestMode::Custom,
+        TestMode::Code,
+        TestMode::Practice,
+    ];
+
+    /// Lowercase name, as used in config files and on the command line.
+    pub fn label(&self) -> &'static str {
+        match self {
+            TestMode::Words => "words",
+            TestMode::Time => "time",
+            TestMode::Quote => "quote",
+            TestMode::Custom => "custom",
+            TestMode::Code => "code",
+            TestMode::Practice => "practice",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct Settings {
     pub test_mode: TestMode,
     pub word_count: usize,
     pub time_limit: u64,
     pub language: String,
     pub lines_to_display: usize,
+    pub punctuation: bool,
+    pub numbers: bool,
+    /// Percentage of generated words replaced by numbers.
+    pub number_rate: usize,
+    pub quote_length: QuoteLength,
+    pub custom_normalize_whitespace: bool,
+    pub custom_ascii_only: bool,
+    /// Words per custom text section, or 0 for the whole text.
+    pub custom_section_words: usize,
+    /// Language to sample code snippets from, or "all".
+    pub code_language: String,
+    /// Race a ghost of the personal best for the current test settings.
+    pub ghost: bool,
+    pub theme: String,
 }
 
 impl Default for Settings {
@@ -33,13 +128,53 @@ impl Default for Settings {
             time_limit: 30,
             language: "english_1k".to_string(),
             lines_to_display: 3,
+            punctuation: false,
+            numbers: false,
+            number_rate: 10,
+            quote_length: QuoteLength::Medium,
+            custom_normalize_whitespace: true,
+            custom_ascii_only: false,
+            custom_section_words: 0,
+            code_language: "all".to_string(),
+            ghost: false,
+            theme: "default".to_string(),
         }
     }
 }
 
-#[derive(Debug, Clone)]
+impl Settings {
+    /// Brings numbers into the ranges `App::modify_setting` keeps them in.
+    pub fn clamped(mut self) -> Self {
+        self.word_count = self.word_count.clamp(10, 200);
+        self.time_limit = self.time_limit.clamp(15, 300);
+        self.lines_to_display = self.lines_to_display.clamp(1, 10);
+        self.number_rate = self.number_rate.clamp(5, 50);
+        self.custom_section_words = self.custom_section_words.min(500);
+        self
+    }
+
+    pub fn text_options(&self) -> TextOptions {
+        TextOptions {
+            punctuation: self.punctuation,
+            numbers: self.numbers,
+            number_rate: self.number_rate,
+        }
+    }
+
+    pub fn custom_options(&self) -> CustomOptions {
+        CustomOptions {
+            normalize_whitespace: self.custom_normalize_whitespace,
+            ascii_only: self.custom_ascii_only,
+            section_words: self.custom_section_words,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
 pub struct UserStats {
     pub total_tests: usize,
+    /// How many of the tests were imported from other tools.
+    pub imported_tests: usize,
     pub average_wpm: f64,
     pub best_wpm: f64,
     pub average_accuracy: f64,
@@ -51,6 +186,7 @@ impl Default for UserStats {
     fn default() -> Self {
         Self {
             total_tests: 0,
+            imported_tests: 0,
             average_wpm: 0.0,
             best_wpm: 0.0,
             average_accuracy: 0.0,
@@ -60,44 +196,181 @@ impl Default for UserStats {
     }
 }
 
+impl UserStats {
+    pub fn from_history(records: &[RunRecord]) -> Self {
+        if records.is_empty() {
+            return Self::default();
+        }
+
+        let count = records.len() as f64;
+        Self {
+            total_tests: records.len(),
+            imported_tests: records.iter().filter(|r| r.imported_from.is_some()).count(),
+            average_wpm: records.iter().map(|r| r.wpm).sum::<f64>() / count,
+            best_wpm: records.iter().map(|r| r.wpm).fold(0.0, f64::max),
+            average_accuracy: records.iter().map(|r| r.accuracy).sum::<f64>() / count,
+            total_words_typed: records.iter().map(|r| r.words_typed).sum(),
+            total_time_seconds: records.iter().map(|r| r.duration_secs).sum::<f64>() as u64,
+        }
+    }
+}
+
 pub struct App {
     pub mode: AppMode,
     pub menu_selection: usize,
     pub settings_selection: usize,
     pub test_text: String,
-    pub current_input: String,
-    pub current_pos: usize,
+    pub engine: TypingEngine,
     pub start_time: Option<Instant>,
     pub end_time: Option<Instant>,
-    pub errors: usize,
+    pub keystrokes: Vec<Keystroke>,
     pub wpm: f64,
+    pub raw_wpm: f64,
     pub accuracy: f64,
+    pub consistency: f64,
+    pub char_counts: CharCounts,
+    pub symbol_accuracy: Option<f64>,
+    pub timeline: Vec<SecondSample>,
     pub settings: Settings,
+    /// Settings as written to the config file, without command line
+    /// overrides or fallbacks for this session.
+    pub saved_settings: Settings,
+    /// Settings when the Settings screen was opened, to tell which ones
+    /// the user changed there.
+    settings_on_open: Settings,
     pub user_stats: UserStats,
+    pub history: Vec<RunRecord>,
+    /// Message for the Stats screen, such as an export result or why a race
+    /// could not start. Cleared when the view changes.
+    pub stats_status: Option<String>,
+    pub stats_view: StatsView,
+    pub key_metric: KeyMetric,
+    /// Per-key statistics over the whole history, refreshed when Stats opens.
+    pub key_stats: HashMap<char, KeyStats>,
+    /// 2 for bigrams, 3 for trigrams.
+    pub ngram_size: usize,
+    /// N-grams with enough attempts to rank, worst first by `key_metric`.
+    pub ngram_stats: Vec<(String, KeyStats)>,
+    pub ngram_selection: usize,
+    /// The n-gram drilled into, with its figures per run.
+    pub ngram_trend: Option<(String, Vec<(u64, KeyStats)>)>,
+    /// Selected row of the Runs view, which lists runs newest first.
+    pub run_selection: usize,
+    pub replay: Option<Replay>,
+    /// The ghost racing the current test, kept for the results.
+    pub ghost: Option<Ghost>,
+    /// Seed given on the command line, used for every generated test.
+    pub fixed_seed: Option<u64>,
+    /// Seed the current test's words were generated from, for generated modes.
+    pub seed: Option<u64>,
     pub language_manager: LanguageManager,
+    pub quote_manager: QuoteManager,
+    /// The quote being typed, in quote mode.
+    pub current_quote: Option<Quote>,
+    /// Text loaded from a file or stdin for custom mode.
+    pub custom_text: Option<CustomText>,
+    /// Index of the next custom section to type.
+    pub custom_next: usize,
+    /// The custom section being typed, as (1-based index, section count).
+    pub custom_section: Option<(usize, usize)>,
+    pub code_manager: CodeManager,
+    pub theme_manager: ThemeManager,
+    /// The snippet being typed, in code mode.
+    pub current_snippet: Option<Snippet>,
+    /// Characters and bigrams the current practice test targets, weakest first.
+    pub practice_targets: Vec<String>,
+    pub warning: Option<String>,
 }
 
 impl App {
     pub fn new() -> Self {
         let language_manager = LanguageManager::new();
-        let settings = Settings::default();
-        let test_text = language_manager.generate_text(&settings.language, settings.word_count);
+        let (mut settings, settings_warning) = config::load_settings();
+        let saved_settings = settings.clone();
+        // Custom mode needs text from the command line, which this session may not have
+        if settings.test_mode == TestMode::Custom {
+            settings.test_mode = TestMode::Words;
+        }
+        let (history, history_warning) = history::load_history();
+        let language_warning = match language_manager.failed_count() {
+            0 => None,
+            n => Some(format!("{} language file(s) failed to load, see Diagnostics", n)),
+        };
+        let unknown_language_warning = language_manager.get_language(&settings.language).is_none().then(|| {
+            let unknown = std::mem::replace(&mut settings.language, Settings::default().language);
+            format!("Unknown language \"{}\", using {}", unknown, settings.language)
+        });
+        let theme_manager = ThemeManager::new();
+        let theme_warning = match theme_manager.failures() {
+            [] if theme_manager.get(&settings.theme).is_none() => {
+                Some(format!("Unknown theme \"{}\", using default", settings.theme))
+            }
+            [] => None,
+            failures => Some(format!("Ignoring theme file(s): {}", failures.join(", "))),
+        };
+        let warnings: Vec<String> = [
+            settings_warning,
+            history_warning,
+            language_warning,
+            unknown_language_warning,
+            theme_warning,
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        let warning = (!warnings.is_empty()).then(|| warnings.join("; "));
+        let test_text = language_manager.generate_text(
+            &settings.language,
+            settings.word_count,
+            &settings.text_options(),
+            rand::random(),
+        );
         
         Self {
             mode: AppMode::Menu,
             menu_selection: 0,
             settings_selection: 0,
+            engine: TypingEngine::new(&test_text),
             test_text,
-            current_input: String::new(),
-            current_pos: 0,
             start_time: None,
             end_time: None,
-            errors: 0,
+            keystrokes: Vec::new(),
             wpm: 0.0,
+            raw_wpm: 0.0,
             accuracy: 0.0,
+            consistency: 0.0,
+            char_counts: CharCounts::default(),
+            symbol_accuracy: None,
+            timeline: Vec::new(),
+            saved_settings,
+            settings_on_open: settings.clone(),
             settings,
-            user_stats: UserStats::default(),
+            user_stats: UserStats::from_history(&history),
+            history,
+            stats_status: None,
+            stats_view: StatsView::Overview,
+            key_metric: KeyMetric::ErrorRate,
+            key_stats: HashMap::new(),
+            ngram_size: 2,
+            ngram_stats: Vec::new(),
+            ngram_selection: 0,
+            ngram_trend: None,
+            run_selection: 0,
+            replay: None,
+            ghost: None,
+            fixed_seed: None,
+            seed: None,
             language_manager,
+            quote_manager: QuoteManager::new(),
+            current_quote: None,
+            custom_text: None,
+            custom_next: 0,
+            custom_section: None,
+            code_manager: CodeManager::new(),
+            theme_manager,
+            current_snippet: None,
+            practice_targets: Vec::new(),
+            warning,
         }
     }
 
@@ -120,7 +393,7 @@ impl App {
     }
 
     pub fn settings_down(&mut self) {
-        if self.settings_selection < 4 {
+        if self.settings_selection < 14 {
             self.settings_selection += 1;
         }
     }
@@ -128,11 +401,12 @@ impl App {
     pub fn modify_setting(&mut self, increase: bool) {
         match self.settings_selection {
             0 => {
-                // Toggle test mode
-                self.settings.test_mode = match self.settings.test_mode {
-                    TestMode::Words => TestMode::Time,
-                    TestMode::Time => TestMode::Words,
-                };
+                // Cycle through test modes
+                let modes: Vec<TestMode> = TestMode::ALL
+                    .into_iter()
+                    .filter(|m| *m != TestMode::Custom || self.custom_text.is_some())
+                    .collect();
+                self.settings.test_mode = cycle(&modes, &self.settings.test_mode, increase);
             }
             1 => {
                 // Modify word count
@@ -169,31 +443,363 @@ impl App {
                     self.settings.lines_to_display -= 1;
                 }
             }
+            5 => {
+                self.settings.punctuation = !self.settings.punctuation;
+            }
+            6 => {
+                self.settings.numbers = !self.settings.numbers;
+            }
+            7 => {
+                // Modify how often numbers appear
+                if increase && self.settings.number_rate < 50 {
+                    self.settings.number_rate += 5;
+                } else if !increase && self.settings.number_rate > 5 {
+                    self.settings.number_rate -= 5;
+                }
+            }
+            8 => {
+                // Cycle through quote lengths
+                self.settings.quote_length = cycle(&QuoteLength::ALL, &self.settings.quote_length, increase);
+            }
+            9 => {
+                self.settings.custom_normalize_whitespace = !self.settings.custom_normalize_whitespace;
+            }
+            10 => {
+                self.settings.custom_ascii_only = !self.settings.custom_ascii_only;
+            }
+            11 => {
+                // Modify custom section size, where 0 means no splitting
+                if increase && self.settings.custom_section_words < 500 {
+                    self.settings.custom_section_words += 25;
+                } else if !increase && self.settings.custom_section_words > 0 {
+                    self.settings.custom_section_words -= 25;
+                }
+            }
+            12 => {
+                // Cycle through code languages
+                let mut languages = vec!["all".to_string()];
+                languages.extend(self.code_manager.available_languages());
+                self.settings.code_language = cycle(&languages, &self.settings.code_language, increase);
+            }
+            13 => {
+                self.settings.ghost = !self.settings.ghost;
+            }
+            14 => {
+                // Cycle through themes, which the screen previews straight away
+                let themes = self.theme_manager.available_themes();
+                self.settings.theme = cycle(&themes, &self.settings.theme, increase);
+            }
             _ => {}
         }
     }
 
+    /// The theme chosen in the settings.
+    pub fn theme(&self) -> &Theme {
+        self.theme_manager.get_or_default(&self.settings.theme)
+    }
+
     pub fn open_settings(&mut self) {
+        self.settings_on_open = self.settings.clone();
         self.mode = AppMode::Settings;
     }
 
+    /// Saves the settings changed on the Settings screen. The rest keep
+    /// their saved values, so command line overrides stay in this session.
+    pub fn close_settings(&mut self) {
+        let result = config::merge_changes(&self.saved_settings, &self.settings_on_open, &self.settings)
+            .and_then(|merged| {
+                config::save_settings(&merged)?;
+                self.saved_settings = merged;
+                Ok(())
+            });
+        if let Err(err) = result {
+            self.warning = Some(format!("Could not save settings: {}", err));
+        }
+        self.return_to_menu();
+    }
+
     pub fn open_stats(&mut self) {
+        self.stats_status = None;
+        self.key_stats = keystats::per_key(&self.history);
+        self.ngram_trend = None;
+        self.refresh_ngrams();
         self.mode = AppMode::Stats;
     }
 
+    /// Recomputes the n-gram list for the current size and sort order.
+    fn refresh_ngrams(&mut self) {
+        let mut ngrams: Vec<(String, KeyStats)> = keystats::per_ngram(&self.history, self.ngram_size)
+            .into_iter()
+            .filter(|(_, s)| s.attempts >= keystats::MIN_ATTEMPTS && s.timed > 0)
+            .collect();
+        let metric = self.key_metric;
+        let value = |s: &KeyStats| match metric {
+            KeyMetric::ErrorRate => s.error_rate(),
+            KeyMetric::Latency => s.average_latency().unwrap_or(0.0),
+        };
+        ngrams.sort_by(|a, b| value(&b.1).total_cmp(&value(&a.1)).then(a.0.cmp(&b.0)));
+        self.ngram_stats = ngrams;
+        self.ngram_selection = self.ngram_selection.min(self.ngram_stats.len().saturating_sub(1));
+    }
+
+    pub fn toggle_ngram_size(&mut self) {
+        if self.stats_view == StatsView::Ngrams && self.ngram_trend.is_none() {
+            self.ngram_size = if self.ngram_size == 2 { 3 } else { 2 };
+            self.ngram_selection = 0;
+            self.refresh_ngrams();
+        }
+    }
+
+    pub fn stats_up(&mut self) {
+        match self.stats_view {
+            StatsView::Ngrams if self.ngram_trend.is_none() => {
+                self.ngram_selection = self.ngram_selection.saturating_sub(1);
+            }
+            StatsView::Runs => self.run_selection = self.run_selection.saturating_sub(1),
+            _ => {}
+        }
+    }
+
+    pub fn stats_down(&mut self) {
+        match self.stats_view {
+            StatsView::Ngrams if self.ngram_trend.is_none() && self.ngram_selection + 1 < self.ngram_stats.len() => {
+                self.ngram_selection += 1;
+            }
+            StatsView::Runs if self.run_selection + 1 < self.history.len() => self.run_selection += 1,
+            _ => {}
+        }
+    }
+
+    /// Enter on the Stats screen: opens or closes an n-gram's trend, replays
+    /// the selected run, or leaves the screen from the other views.
+    pub fn stats_select(&mut self) {
+        match self.stats_view {
+            StatsView::Ngrams if self.ngram_trend.is_some() => self.ngram_trend = None,
+            StatsView::Ngrams => {
+                if let Some((ngram, _)) = self.ngram_stats.get(self.ngram_selection) {
+                    let trend = keystats::ngram_trend(&self.history, ngram);
+                    self.ngram_trend = Some((ngram.clone(), trend));
+                }
+            }
+            StatsView::Runs => {
+                if let Some(&run) = self.runs_by_date().get(self.run_selection) {
+                    self.start_replay(run);
+                }
+            }
+            _ => self.return_to_menu(),
+        }
+    }
+
+    /// Indices into `history`, newest run first.
+    pub fn runs_by_date(&self) -> Vec<usize> {
+        let mut runs: Vec<usize> = (0..self.history.len()).collect();
+        runs.sort_by_key(|&i| std::cmp::Reverse(self.history[i].timestamp));
+        runs
+    }
+
+    /// Plays back run `run` of the history, if it was recorded with its keys.
+    pub fn start_replay(&mut self, run: usize) {
+        if let Some(mut replay) = self.history.get(run).and_then(Replay::new) {
+            replay.return_to = self.mode.clone();
+            self.replay = Some(replay);
+            self.mode = AppMode::Replay;
+        }
+    }
+
+    /// Starts a test on the text of run `run`, in its mode, raced by a ghost
+    /// of that run. Runs saved without their text cannot be raced.
+    pub fn start_race(&mut self, run: usize) {
+        let Some(record) = self.history.get(run) else {
+            return;
+        };
+        let Some(text) = record.text.clone() else {
+            self.stats_status = Some("This run was saved without its text, so it can't be raced".to_string());
+            return;
+        };
+        let record = record.clone();
+
+        self.settings.test_mode = record.mode.clone();
+        self.settings.word_count = record.word_count;
+        self.settings.time_limit = record.time_limit;
+        if self.language_manager.get_language(&record.language).is_some() {
+            self.settings.language = record.language.clone();
+        }
+        self.current_quote = record.quote_id.and_then(|id| self.quote_manager.get(id)).cloned();
+        self.current_snippet = (record.mode == TestMode::Code).then(|| Snippet {
+            source: record.source.clone().unwrap_or_default(),
+            language: record.language.clone(),
+            text: text.clone(),
+        });
+        if record.mode == TestMode::Custom {
+            // Later custom tests carry on from the raced text
+            let source = record.source.clone().unwrap_or_else(|| "raced run".to_string());
+            self.set_custom_text(CustomText { source, raw: text.clone() });
+        }
+        self.custom_section = None;
+        self.practice_targets.clear();
+        self.seed = record.seed;
+        self.test_text = text;
+        self.begin_test();
+        self.ghost = Some(Ghost::of_run(&record));
+    }
+
+    /// The fastest earlier run with the same mode, language and length as
+    /// the current settings, or of the same text in custom and code modes.
+    fn personal_best(&self) -> Option<&RunRecord> {
+        let settings = &self.settings;
+        self.history
+            .iter()
+            .filter(|r| r.mode == settings.test_mode && r.imported_from.is_none())
+            .filter(|r| match settings.test_mode {
+                TestMode::Words | TestMode::Practice => {
+                    r.language == settings.language && r.word_count == settings.word_count
+                }
+                TestMode::Time => r.language == settings.language && r.time_limit == settings.time_limit,
+                TestMode::Quote => r.quote_id.is_some() && r.quote_id == self.current_quote.as_ref().map(|q| q.id),
+                // Files and snippets vary, so only a run of the same text compares
+                TestMode::Custom | TestMode::Code => r.text.as_deref() == Some(self.test_text.as_str()),
+            })
+            .max_by(|a, b| a.wpm.total_cmp(&b.wpm))
+    }
+
+    pub fn close_replay(&mut self) {
+        if let Some(replay) = self.replay.take() {
+            self.mode = replay.return_to;
+        }
+    }
+
+    /// Esc on the Stats screen: closes an open trend, or leaves the screen.
+    pub fn stats_back(&mut self) {
+        if self.ngram_trend.is_some() {
+            self.ngram_trend = None;
+        } else {
+            self.return_to_menu();
+        }
+    }
+
+    pub fn next_stats_view(&mut self) {
+        self.ngram_trend = None;
+        self.stats_status = None;
+        self.stats_view = cycle(&StatsView::ALL, &self.stats_view, true);
+    }
+
+    pub fn toggle_key_metric(&mut self) {
+        self.key_metric = match self.key_metric {
+            KeyMetric::ErrorRate => KeyMetric::Latency,
+            KeyMetric::Latency => KeyMetric::ErrorRate,
+        };
+        self.refresh_ngrams();
+    }
+
+    /// Writes the whole history to a file in the exports directory.
+    pub fn export_history(&mut self, format: ExportFormat) {
+        self.stats_status = Some(match export::export_to_data_dir(&self.history, format) {
+            Ok(path) => format!("Exported {} run(s) to {}", self.history.len(), path.display()),
+            Err(err) => format!("Export failed: {}", err),
+        });
+    }
+
+    pub fn open_diagnostics(&mut self) {
+        self.mode = AppMode::Diagnostics;
+    }
+
+    /// Switches to custom mode, typing `text` from its first section.
+    pub fn set_custom_text(&mut self, text: CustomText) {
+        self.custom_text = Some(text);
+        self.custom_next = 0;
+        self.settings.test_mode = TestMode::Custom;
+    }
+
     pub fn start_test(&mut self) {
-        let word_count = match self.settings.test_mode {
-            TestMode::Words => self.settings.word_count,
-            TestMode::Time => 200,
+        self.current_quote = None;
+        self.custom_section = None;
+        self.current_snippet = None;
+        self.practice_targets.clear();
+        let seed = self.fixed_seed.unwrap_or_else(rand::random);
+        self.seed = None;
+        self.test_text = match self.settings.test_mode {
+            TestMode::Custom => self.next_custom_section(),
+            TestMode::Code => match self.code_manager.random_snippet(&self.settings.code_language) {
+                Some(snippet) => {
+                    let text = snippet.text.clone();
+                    self.current_snippet = Some(snippet);
+                    text
+                }
+                None => "fn main() {\n    println!(\"hello, world\");\n}".to_string(),
+            },
+            TestMode::Quote => match self.quote_manager.random_quote(self.settings.quote_length) {
+                Some(quote) => {
+                    self.current_quote = Some(quote.clone());
+                    quote.text.clone()
+                }
+                None => "the quick brown fox jumps over the lazy dog".to_string(),
+            },
+            TestMode::Practice => {
+                // Re-evaluated every test, so the targets follow the last run
+                let spots = WeakSpots::from_history(&self.history);
+                self.practice_targets = spots.targets();
+                self.seed = Some(seed);
+                if spots.is_empty() {
+                    self.language_manager.generate_text(
+                        &self.settings.language,
+                        self.settings.word_count,
+                        &self.settings.text_options(),
+                        seed,
+                    )
+                } else {
+                    self.language_manager.generate_weighted_text(
+                        &self.settings.language,
+                        self.settings.word_count,
+                        &self.settings.text_options(),
+                        seed,
+                        |word| spots.word_weight(word),
+                    )
+                }
+            }
+            TestMode::Words | TestMode::Time => {
+                let word_count = match self.settings.test_mode {
+                    TestMode::Time => 200,
+                    _ => self.settings.word_count,
+                };
+                self.seed = Some(seed);
+                self.language_manager.generate_text(
+                    &self.settings.language,
+                    word_count,
+                    &self.settings.text_options(),
+                    seed,
+                )
+            }
         };
-        self.test_text = self.language_manager.generate_text(&self.settings.language, word_count);
         
+        self.ghost = match self.personal_best() {
+            Some(best) if self.settings.ghost => Some(Ghost::new(best, "personal best".to_string())),
+            _ => None,
+        };
+        self.begin_test();
+    }
+
+    /// Resets the typing state for `test_text` and switches to the test.
+    fn begin_test(&mut self) {
         self.mode = AppMode::Test;
-        self.current_input.clear();
-        self.current_pos = 0;
+        self.engine = TypingEngine::new(&self.test_text);
         self.start_time = Some(Instant::now());
         self.end_time = None;
-        self.errors = 0;
+        self.keystrokes.clear();
+    }
+
+    /// Takes the next section of the custom text, wrapping around at the end.
+    fn next_custom_section(&mut self) -> String {
+        let sections = match &self.custom_text {
+            Some(text) => text.sections(&self.settings.custom_options()),
+            None => Vec::new(),
+        };
+        if sections.is_empty() {
+            return "the quick brown fox jumps over the lazy dog".to_string();
+        }
+        let idx = self.custom_next % sections.len();
+        self.custom_next = idx + 1;
+        self.custom_section = Some((idx + 1, sections.len()));
+        sections[idx].clone()
     }
 
     pub fn type_char(&mut self, c: char) {
@@ -201,78 +807,158 @@ impl App {
             self.start_time = Some(Instant::now());
         }
 
-        // Check time limit for time mode
-        if self.settings.test_mode == TestMode::Time {
-            if let Some(start) = self.start_time {
-                if start.elapsed().as_secs() >= self.settings.time_limit {
-                    self.finish_test();
-                    return;
-                }
-            }
+        // A key pressed after the time limit must not count
+        self.tick();
+        if self.mode != AppMode::Test {
+            return;
         }
 
-        if self.current_pos < self.test_text.len() {
-            self.current_input.push(c);
-            
-            let expected = self.test_text.chars().nth(self.current_pos).unwrap();
-            if c != expected {
-                self.errors += 1;
-            }
-            
-            self.current_pos += 1;
-
-            if self.settings.test_mode == TestMode::Words {
-                let words_typed = self.current_input.split_whitespace().count();
-                if words_typed >= self.settings.word_count {
-                    self.finish_test();
-                    return;
-                }
-            }
+        let expected = self.engine.expected();
+        if let Some(correct) = self.engine.type_char(c) {
+            self.record_keystroke(KeyAction::Char { typed: c, expected, correct });
 
-            if self.current_pos >= self.test_text.len() {
+            if self.engine.is_complete() {
                 self.finish_test();
             }
         }
     }
 
     pub fn backspace(&mut self) {
-        if self.current_pos > 0 {
-            self.current_input.pop();
-            self.current_pos -= 1;
+        if self.engine.backspace() {
+            self.record_keystroke(KeyAction::Backspace);
         }
     }
 
+    fn record_keystroke(&mut self, action: KeyAction) {
+        let at_ms = self
+            .start_time
+            .map(|start| start.elapsed().as_millis() as u64)
+            .unwrap_or(0);
+        self.keystrokes.push(Keystroke { at_ms, action });
+    }
+
+    /// Advances the clock. Ends a time-mode test exactly at its time limit,
+    /// and moves any replay along.
+    pub fn tick(&mut self) {
+        if self.mode == AppMode::Replay
+            && let Some(replay) = &mut self.replay
+        {
+            replay.tick();
+        }
+        if self.mode == AppMode::Test
+            && let (Some(ghost), Some(start)) = (&mut self.ghost, self.start_time)
+        {
+            ghost.advance(start.elapsed().as_secs_f64() * 1000.0);
+        }
+        if self.mode == AppMode::Test
+            && self.settings.test_mode == TestMode::Time
+            && let Some(start) = self.start_time
+        {
+            let deadline = start + Duration::from_secs(self.settings.time_limit);
+            if Instant::now() >= deadline {
+                self.finish_test_at(deadline);
+            }
+        }
+    }
+
+    /// Seconds left in a time-mode test, or `None` in other modes.
+    pub fn time_remaining(&self) -> Option<u64> {
+        if self.settings.test_mode != TestMode::Time {
+            return None;
+        }
+        let elapsed = self.start_time.map(|s| s.elapsed().as_secs()).unwrap_or(0);
+        Some(self.settings.time_limit.saturating_sub(elapsed))
+    }
+
     fn finish_test(&mut self) {
-        self.end_time = Some(Instant::now());
+        self.finish_test_at(Instant::now());
+    }
+
+    fn finish_test_at(&mut self, end: Instant) {
+        self.end_time = Some(end);
+        self.char_counts = self.engine.counts();
         if let Some(start) = self.start_time {
             let duration = self.end_time.unwrap().duration_since(start);
-            let minutes = duration.as_secs_f64() / 60.0;
-            let words = self.current_input.split_whitespace().count() as f64;
-            self.wpm = words / minutes;
-            self.accuracy = ((self.current_input.len() - self.errors) as f64 / self.current_input.len() as f64) * 100.0;
-            
-            self.user_stats.total_tests += 1;
-            self.user_stats.total_words_typed += words as usize;
-            self.user_stats.total_time_seconds += duration.as_secs();
-            
-            if self.wpm > self.user_stats.best_wpm {
-                self.user_stats.best_wpm = self.wpm;
+            let words = self.engine.words_typed();
+            let correct_chars = self.engine.correct_chars();
+            let result = metrics::summarize(&self.keystrokes, correct_chars, duration.as_secs_f64());
+            self.wpm = result.wpm;
+            self.raw_wpm = result.raw_wpm;
+            self.accuracy = result.accuracy;
+            self.consistency = result.consistency;
+            self.symbol_accuracy = metrics::symbol_accuracy(&self.keystrokes);
+            self.timeline = metrics::timeline(&self.keystrokes, duration.as_secs_f64());
+
+            let record = RunRecord {
+                timestamp: history::now_timestamp(),
+                mode: self.settings.test_mode.clone(),
+                language: match &self.current_snippet {
+                    Some(snippet) => snippet.language.clone(),
+                    None => self.settings.language.clone(),
+                },
+                word_count: self.settings.word_count,
+                time_limit: self.settings.time_limit,
+                wpm: self.wpm,
+                raw_wpm: self.raw_wpm,
+                accuracy: self.accuracy,
+                consistency: self.consistency,
+                errors: self.char_counts.incorrect + self.char_counts.extra + self.char_counts.missed,
+                corrected_errors: self.char_counts.corrected,
+                words_typed: words,
+                duration_secs: duration.as_secs_f64(),
+                quote_id: self.current_quote.as_ref().map(|q| q.id),
+                source: self.text_source(),
+                symbol_accuracy: self.symbol_accuracy,
+                imported_from: None,
+                keystrokes: self.keystrokes.clone(),
+                text: Some(self.test_text.clone()),
+                seed: self.seed,
+            };
+            if let Err(err) = history::append_run(&record) {
+                self.warning = Some(format!("Could not save test history: {}", err));
             }
-            
-            let total_wpm = self.user_stats.average_wpm * (self.user_stats.total_tests - 1) as f64 + self.wpm;
-            self.user_stats.average_wpm = total_wpm / self.user_stats.total_tests as f64;
-            
-            let total_acc = self.user_stats.average_accuracy * (self.user_stats.total_tests - 1) as f64 + self.accuracy;
-            self.user_stats.average_accuracy = total_acc / self.user_stats.total_tests as f64;
+            self.history.push(record);
+            self.user_stats = UserStats::from_history(&self.history);
         }
         self.mode = AppMode::Results;
     }
 
     pub fn return_to_menu(&mut self) {
         self.mode = AppMode::Menu;
-        self.current_input.clear();
-        self.current_pos = 0;
+        self.engine = TypingEngine::default();
         self.start_time = None;
         self.end_time = None;
     }
+
+    /// Where the current test's text came from, for custom and code tests.
+    pub fn text_source(&self) -> Option<String> {
+        if let Some(snippet) = &self.current_snippet {
+            return Some(snippet.source.clone());
+        }
+        self.custom_section
+            .and(self.custom_text.as_ref())
+            .map(|t| t.source.clone())
+    }
+
+    /// Best WPM on the current quote from runs before the one just finished.
+    pub fn previous_quote_best(&self) -> Option<f64> {
+        let quote_id = self.current_quote.as_ref()?.id;
+        let earlier = &self.history[..self.history.len().saturating_sub(1)];
+        earlier
+            .iter()
+            .filter(|r| r.quote_id == Some(quote_id))
+            .map(|r| r.wpm)
+            .reduce(f64::max)
+    }
+}
+
+/// Returns the option after (or before) `current` in `options`, wrapping around.
+fn cycle<T: Clone + PartialEq>(options: &[T], current: &T, forward: bool) -> T {
+    let idx = options.iter().position(|o| o == current).unwrap_or(0);
+    let next = if forward {
+        (idx + 1) % options.len()
+    } else {
+        (idx + options.len() - 1) % options.len()
+    };
+    options[next].clone()
 }
@@ -0,0 +1,61 @@
+use serde::Serialize;
+use super::export;
+use super::history::RunRecord;
+use super::state::{TestMode, UserStats};
+
+/// Aggregates for one mode and language combination.
+#[derive(Debug, Clone, Serialize)]
+pub struct GroupStats {
+    pub mode: TestMode,
+    pub language: String,
+    #[serde(flatten)]
+    pub stats: UserStats,
+}
+
+/// Everything `remytype stats` reports.
+#[derive(Debug, Clone, Serialize)]
+pub struct HistorySummary {
+    pub overall: UserStats,
+    pub by_mode_language: Vec<GroupStats>,
+    /// The most recent runs, newest first, in the export schema.
+    #[serde(serialize_with = "export::serialize_rows")]
+    pub recent: Vec<RunRecord>,
+}
+
+/// Summarises the history with the same aggregation as the Stats screen.
+pub fn summarize(records: &[RunRecord], recent: usize) -> HistorySummary {
+    let mut keys: Vec<(TestMode, String)> = Vec::new();
+    for record in records {
+        let key = (record.mode.clone(), record.language.clone());
+        if !keys.contains(&key) {
+            keys.push(key);
+        }
+    }
+    keys.sort_by(|a, b| (a.0.label(), &a.1).cmp(&(b.0.label(), &b.1)));
+
+    let by_mode_language = keys
+        .into_iter()
+        .map(|(mode, language)| {
+            let group: Vec<RunRecord> = records
+                .iter()
+                .filter(|r| r.mode == mode && r.language == language)
+                .cloned()
+                .collect();
+            GroupStats {
+                stats: UserStats::from_history(&group),
+                mode,
+                language,
+            }
+        })
+        .collect();
+
+    // Imported runs are appended after newer ones, so order by when they happened
+    let mut by_date: Vec<&RunRecord> = records.iter().collect();
+    by_date.sort_by_key(|r| std::cmp::Reverse(r.timestamp));
+
+    HistorySummary {
+        overall: UserStats::from_history(records),
+        by_mode_language,
+        recent: by_date.into_iter().take(recent).cloned().collect(),
+    }
+}
@@ -0,0 +1,311 @@
+use std::fs;
+use std::io::{self, IsTerminal, Read};
+use std::path::Path;
+use crate::app::custom::CustomText;
+use crate::app::export::ExportFormat;
+use crate::app::{App, TestMode};
+use crate::quotes::QuoteLength;
+
+pub const USAGE: &str = "\
+remytype - a terminal typing test
+
+Usage: remytype [OPTIONS] [FILE]
+       remytype stats [--json] [--last <N>]
+       remytype export [--format <FORMAT>] [--output <PATH>]
+       remytype import [--dry-run] <CSV>
+
+Commands:
+  stats                     Print a summary of your test history and exit
+      --json                Print the summary as JSON
+      --last <N>            Number of recent runs to list (default 10)
+  export                    Write the full run history as CSV or JSON and exit
+      --format <FORMAT>     csv or json (default: from the output name, else csv)
+  -o, --output <PATH>       File to write instead of stdout
+  import                    Add runs from a CSV export (remytype, Monkeytype, ...)
+      --dry-run             Report what would be imported without saving it
+
+Arguments:
+  [FILE]                    Type the contents of FILE (custom mode); '-' reads stdin
+
+Options:
+  -m, --mode <MODE>         Test mode: words, time, quote, custom, code or practice
+  -t, --time <SECONDS>      Time mode with this time limit
+  -w, --words <COUNT>       Words mode with this many words
+  -l, --language <NAME>     Word list to generate text from
+  -q, --quote [LENGTH]      Quote mode, optionally short, medium, long or thicc
+  -f, --file <PATH>         Custom mode with text from PATH ('-' reads stdin)
+  -p, --punctuation         Mix punctuation into generated text
+  -n, --numbers             Mix numbers into generated text
+  -s, --seed <N>            Generate the same words every time for this seed
+  -h, --help                Print this help
+  -V, --version             Print the version
+
+Any test option skips the menu and starts the test straight away.
+Options override the saved settings for this session.";
+
+/// Test settings given on the command line.
+#[derive(Debug, Clone, Default)]
+pub struct RunArgs {
+    pub mode: Option<TestMode>,
+    pub time: Option<u64>,
+    pub words: Option<usize>,
+    pub language: Option<String>,
+    pub quote_length: Option<QuoteLength>,
+    pub file: Option<String>,
+    pub punctuation: bool,
+    pub numbers: bool,
+    pub seed: Option<u64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct StatsArgs {
+    pub json: bool,
+    pub last: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct ExportArgs {
+    pub format: ExportFormat,
+    pub output: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ImportArgs {
+    pub file: String,
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Clone)]
+pub enum Command {
+    Run(RunArgs),
+    Stats(StatsArgs),
+    Export(ExportArgs),
+    Import(ImportArgs),
+    Help,
+    Version,
+}
+
+/// Parses the arguments after the program name.
+pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Command, String> {
+    let mut args = args.into_iter().peekable();
+    match args.peek().map(String::as_str) {
+        Some("stats") => {
+            args.next();
+            return parse_stats(args);
+        }
+        Some("export") => {
+            args.next();
+            return parse_export(args);
+        }
+        Some("import") => {
+            args.next();
+            return parse_import(args);
+        }
+        _ => {}
+    }
+
+    let mut run = RunArgs::default();
+
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| args.next().ok_or_else(|| format!("{} needs a value", name));
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "-V" | "--version" => return Ok(Command::Version),
+            "-m" | "--mode" => run.mode = Some(parse_mode(&value(&arg)?)?),
+            "-t" | "--time" => run.time = Some(parse_number(&arg, &value(&arg)?)?),
+            "-w" | "--words" => run.words = Some(parse_number(&arg, &value(&arg)?)?),
+            "-l" | "--language" => run.language = Some(value(&arg)?),
+            "-f" | "--file" => run.file = Some(value(&arg)?),
+            "-p" | "--punctuation" => run.punctuation = true,
+            "-n" | "--numbers" => run.numbers = true,
+            "-s" | "--seed" => run.seed = Some(parse_number(&arg, &value(&arg)?)?),
+            "-q" | "--quote" => {
+                // The length is optional, so only take the next argument if it is one
+                let length = args.peek().and_then(|next| parse_quote_length(next));
+                if length.is_some() {
+                    args.next();
+                }
+                run.quote_length = Some(length.unwrap_or(QuoteLength::Medium));
+            }
+            "-" => run.file = Some(arg),
+            other if other.starts_with('-') => return Err(format!("unknown option '{}'", other)),
+            _ if run.file.is_none() => run.file = Some(arg),
+            _ => return Err(format!("unexpected argument '{}'", arg)),
+        }
+    }
+
+    Ok(Command::Run(run))
+}
+
+fn parse_stats<I: Iterator<Item = String>>(mut args: I) -> Result<Command, String> {
+    let mut stats = StatsArgs { json: false, last: 10 };
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "--json" => stats.json = true,
+            "--last" => {
+                let value = args.next().ok_or("--last needs a value")?;
+                stats.last = parse_number(&arg, &value)?;
+            }
+            other => return Err(format!("unexpected argument '{}' for stats", other)),
+        }
+    }
+    Ok(Command::Stats(stats))
+}
+
+fn parse_export<I: Iterator<Item = String>>(mut args: I) -> Result<Command, String> {
+    let mut format = None;
+    let mut output: Option<String> = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "--format" => {
+                let value = args.next().ok_or("--format needs a value")?;
+                format = Some(match value.as_str() {
+                    "csv" => ExportFormat::Csv,
+                    "json" => ExportFormat::Json,
+                    _ => return Err(format!("unknown export format '{}' (expected csv or json)", value)),
+                });
+            }
+            "-o" | "--output" => output = Some(args.next().ok_or_else(|| format!("{} needs a value", arg))?),
+            other => return Err(format!("unexpected argument '{}' for export", other)),
+        }
+    }
+
+    let format = format
+        .or_else(|| output.as_deref().and_then(|o| ExportFormat::from_path(Path::new(o))))
+        .unwrap_or(ExportFormat::Csv);
+    Ok(Command::Export(ExportArgs { format, output }))
+}
+
+fn parse_import<I: Iterator<Item = String>>(args: I) -> Result<Command, String> {
+    let mut file = None;
+    let mut dry_run = false;
+    for arg in args {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "--dry-run" => dry_run = true,
+            other if other.starts_with('-') => return Err(format!("unexpected argument '{}' for import", other)),
+            _ if file.is_none() => file = Some(arg),
+            _ => return Err(format!("unexpected argument '{}' for import", arg)),
+        }
+    }
+    let file = file.ok_or("import needs a CSV file")?;
+    Ok(Command::Import(ImportArgs { file, dry_run }))
+}
+
+fn parse_mode(value: &str) -> Result<TestMode, String> {
+    TestMode::ALL
+        .into_iter()
+        .find(|m| m.label() == value)
+        .ok_or_else(|| format!("unknown mode '{}' (expected words, time, quote, custom, code or practice)", value))
+}
+
+fn parse_quote_length(value: &str) -> Option<QuoteLength> {
+    QuoteLength::ALL.into_iter().find(|l| l.label() == value)
+}
+
+fn parse_number<T: std::str::FromStr>(option: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("{} expects a number, got '{}'", option, value))
+}
+
+impl RunArgs {
+    /// Whether any option asks for a particular test, so the menu is skipped.
+    pub fn configures_test(&self) -> bool {
+        self.mode.is_some()
+            || self.time.is_some()
+            || self.words.is_some()
+            || self.language.is_some()
+            || self.quote_length.is_some()
+            || self.file.is_some()
+            || self.punctuation
+            || self.numbers
+            || self.seed.is_some()
+    }
+
+    /// Applies the options on top of the loaded settings. Returns whether the
+    /// app should start straight into a test.
+    pub fn apply(&self, app: &mut App) -> Result<bool, String> {
+        let custom_text = self.read_custom_text()?;
+        let starts_test = self.configures_test() || custom_text.is_some();
+
+        // Custom text first, so the flags below choose the mode
+        if let Some(text) = custom_text {
+            app.set_custom_text(text);
+        }
+
+        if let Some(language) = &self.language {
+            if app.language_manager.get_language(language).is_none() {
+                return Err(format!(
+                    "unknown language '{}' (available: {})",
+                    language,
+                    app.language_manager.available_languages().join(", ")
+                ));
+            }
+            app.settings.language = language.clone();
+        }
+        if let Some(time) = self.time {
+            app.settings.time_limit = time.max(1);
+            app.settings.test_mode = TestMode::Time;
+        }
+        if let Some(words) = self.words {
+            app.settings.word_count = words.max(1);
+            app.settings.test_mode = TestMode::Words;
+        }
+        if let Some(length) = self.quote_length {
+            app.settings.quote_length = length;
+            app.settings.test_mode = TestMode::Quote;
+        }
+        app.fixed_seed = self.seed;
+        app.settings.punctuation |= self.punctuation;
+        app.settings.numbers |= self.numbers;
+        if let Some(mode) = &self.mode {
+            if *mode == TestMode::Custom && app.custom_text.is_none() {
+                return Err("custom mode needs a FILE, --file or text piped on stdin".to_string());
+            }
+            app.settings.test_mode = mode.clone();
+        }
+
+        Ok(starts_test)
+    }
+
+    /// Whether an option picks a test mode other than custom.
+    fn selects_mode(&self) -> bool {
+        self.time.is_some()
+            || self.words.is_some()
+            || self.quote_length.is_some()
+            || self.mode.as_ref().is_some_and(|m| *m != TestMode::Custom)
+    }
+
+    /// Reads practice text from the given file (`-` for stdin), or from stdin
+    /// when it is piped in and no other mode was asked for.
+    fn read_custom_text(&self) -> Result<Option<CustomText>, String> {
+        let from_stdin = || -> Result<Option<CustomText>, String> {
+            let mut raw = String::new();
+            io::stdin()
+                .read_to_string(&mut raw)
+                .map_err(|e| format!("could not read stdin: {}", e))?;
+            // An empty stdin (e.g. /dev/null) is not a request for custom text
+            if raw.trim().is_empty() {
+                return Ok(None);
+            }
+            Ok(Some(CustomText { source: "stdin".to_string(), raw }))
+        };
+
+        match self.file.as_deref() {
+            Some("-") => from_stdin(),
+            Some(path) => {
+                let raw = fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+                let source = Path::new(path)
+                    .file_name()
+                    .map(|n| n.to_string_lossy().into_owned())
+                    .unwrap_or_else(|| path.to_string());
+                Ok(Some(CustomText { source, raw }))
+            }
+            None if !io::stdin().is_terminal() && !self.selects_mode() => from_stdin(),
+            None => Ok(None),
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use std::fs;
+use super::models::{Snippet, SourceFile};
+use crate::app::paths;
+use rand::prelude::IndexedRandom;
+
+/// Lines of code per snippet.
+const SNIPPET_LINES: usize = 8;
+
+pub struct CodeManager {
+    files: Vec<SourceFile>,
+}
+
+impl CodeManager {
+    pub fn new() -> Self {
+        let mut files = vec![
+            SourceFile::new("sample.rs", include_str!("../../data/code/sample.rs")),
+            SourceFile::new("sample.py", include_str!("../../data/code/sample.py")),
+            SourceFile::new("sample.js", include_str!("../../data/code/sample.js")),
+        ];
+        files.extend(Self::load_user_files());
+        Self { files }
+    }
+
+    /// Reads every UTF-8 file in the user's code directory.
+    fn load_user_files() -> Vec<SourceFile> {
+        let Some(entries) = paths::code_dir().and_then(|dir| fs::read_dir(dir).ok()) else {
+            return Vec::new();
+        };
+        let mut paths: Vec<_> = entries
+            .filter_map(|e| e.ok().map(|e| e.path()))
+            .filter(|p| p.is_file())
+            .collect();
+        paths.sort();
+
+        paths
+            .iter()
+            .filter_map(|path| {
+                let contents = fs::read_to_string(path).ok()?;
+                let name = path.file_name()?.to_string_lossy();
+                Some(SourceFile::new(&name, &contents))
+            })
+            .filter(|file| file.lines.iter().any(|l| !l.trim().is_empty()))
+            .collect()
+    }
+
+    /// Languages with at least one source file, sorted.
+    pub fn available_languages(&self) -> Vec<String> {
+        let mut languages: Vec<String> = self.files.iter().map(|f| f.language.clone()).collect();
+        languages.sort();
+        languages.dedup();
+        languages
+    }
+
+    /// Picks a random run of lines from a file in `language` ("all" for any),
+    /// with the common indentation removed.
+    pub fn random_snippet(&self, language: &str) -> Option<Snippet> {
+        let mut rng = rand::rng();
+        let candidates: Vec<&SourceFile> = self
+            .files
+            .iter()
+            .filter(|f| language == "all" || f.language == language)
+            .collect();
+        let file = candidates.choose(&mut rng)?;
+
+        // Start on a non-blank line that is indented no deeper than the rest
+        // of the snippet, so the first line keeps its place in the layout
+        let window = |start: usize| -> Vec<&str> {
+            let end = (start + SNIPPET_LINES).min(file.lines.len());
+            file.lines[start..end]
+                .iter()
+                .map(|l| l.trim_end())
+                .filter(|l| !l.is_empty())
+                .collect()
+        };
+        let starts: Vec<usize> = (0..file.lines.len())
+            .filter(|&i| !file.lines[i].trim().is_empty())
+            .filter(|&i| min_indent(&window(i)) == indent_of(&file.lines[i]))
+            .collect();
+        let start = *starts.choose(&mut rng)?;
+        let end = (start + SNIPPET_LINES).min(file.lines.len());
+        let lines = window(start);
+        let indent = min_indent(&lines);
+        let text = lines
+            .iter()
+            .map(|l| &l[indent..])
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        Some(Snippet {
+            source: format!("{}:{}-{}", file.name, start + 1, end),
+            language: file.language.clone(),
+            text,
+        })
+    }
+}
+
+/// Leading spaces, which are one byte each so the count can slice the line.
+/// Tabs are expanded when the file is loaded; other whitespace is content.
+fn indent_of(line: &str) -> usize {
+    line.len() - line.trim_start_matches(' ').len()
+}
+
+fn min_indent(lines: &[&str]) -> usize {
+    lines.iter().map(|l| indent_of(l)).min().unwrap_or(0)
+}
@@ -0,0 +1,5 @@
+mod manager;
+mod models;
+
+pub use manager::CodeManager;
+pub use models::Snippet;
@@ -0,0 +1,45 @@
+/// A source file snippets are sampled from.
+#[derive(Debug, Clone)]
+pub struct SourceFile {
+    pub name: String,
+    pub language: String,
+    pub lines: Vec<String>,
+}
+
+/// A few consecutive lines of code to type.
+#[derive(Debug, Clone)]
+pub struct Snippet {
+    /// File name and line range, e.g. "sample.rs:12-21".
+    pub source: String,
+    pub language: String,
+    pub text: String,
+}
+
+impl SourceFile {
+    pub fn new(name: &str, contents: &str) -> Self {
+        let extension = name.rsplit_once('.').map(|(_, ext)| ext).unwrap_or("");
+        Self {
+            name: name.to_string(),
+            language: language_for_extension(extension),
+            lines: contents.lines().map(|l| l.replace('\t', "    ")).collect(),
+        }
+    }
+}
+
+fn language_for_extension(extension: &str) -> String {
+    match extension {
+        "rs" => "rust",
+        "py" => "python",
+        "js" | "mjs" | "cjs" => "javascript",
+        "ts" => "typescript",
+        "go" => "go",
+        "c" | "h" => "c",
+        "cpp" | "cc" | "hpp" => "c++",
+        "java" => "java",
+        "rb" => "ruby",
+        "sh" => "shell",
+        "" => "text",
+        other => other,
+    }
+    .to_string()
+}
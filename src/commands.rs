@@ -0,0 +1,154 @@
+use std::fmt::Write as _;
+use std::fs;
+use std::io::{self, Write as _};
+use std::path::Path;
+use crate::app::{export, import};
+use crate::app::history::{self, RunRecord};
+use crate::app::state::UserStats;
+use crate::app::summary::{self, HistorySummary};
+use crate::cli::{ExportArgs, ImportArgs, StatsArgs};
+
+/// Prints a summary of the saved history for `remytype stats`.
+pub fn stats(args: &StatsArgs) -> Result<(), Box<dyn std::error::Error>> {
+    let (records, warning) = history::load_history();
+    if let Some(warning) = warning {
+        eprintln!("remytype: {}", warning);
+    }
+    let summary = summary::summarize(&records, args.last);
+
+    let mut out = String::new();
+    if args.json {
+        writeln!(out, "{}", serde_json::to_string_pretty(&summary)?)?;
+    } else if records.is_empty() {
+        writeln!(out, "No tests recorded yet.")?;
+    } else {
+        write_summary(&mut out, &summary)?;
+    }
+
+    print(&out)
+}
+
+/// Writes the full history for `remytype export`, to a file or stdout.
+pub fn export(args: &ExportArgs) -> Result<(), Box<dyn std::error::Error>> {
+    let (records, warning) = history::load_history();
+    if let Some(warning) = warning {
+        eprintln!("remytype: {}", warning);
+    }
+    let out = export::render(&records, args.format)?;
+
+    match &args.output {
+        Some(path) => {
+            fs::write(path, out).map_err(|e| format!("{}: {}", path, e))?;
+            eprintln!("Exported {} run(s) to {}", records.len(), path);
+            Ok(())
+        }
+        None => print(&out),
+    }
+}
+
+/// Adds the runs from another tool's CSV export to the history.
+pub fn import(args: &ImportArgs) -> Result<(), Box<dyn std::error::Error>> {
+    let text = fs::read_to_string(&args.file).map_err(|e| format!("{}: {}", args.file, e))?;
+    let (records, warning) = history::load_history();
+    if let Some(warning) = warning {
+        eprintln!("remytype: {}", warning);
+    }
+    let origin = Path::new(&args.file)
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| args.file.clone());
+    let report = import::import_csv(&text, &origin, &records).map_err(|e| format!("{}: {}", args.file, e))?;
+
+    if !args.dry_run {
+        history::append_runs(&report.imported)?;
+    }
+    println!(
+        "{} {} run(s) from {}",
+        if args.dry_run { "Would import" } else { "Imported" },
+        report.imported.len(),
+        args.file
+    );
+    if report.duplicates > 0 {
+        println!("Skipped {} run(s) already in the history", report.duplicates);
+    }
+    if report.invalid > 0 {
+        println!("Skipped {} row(s) without a usable timestamp, wpm or accuracy", report.invalid);
+    }
+    Ok(())
+}
+
+fn print(out: &str) -> Result<(), Box<dyn std::error::Error>> {
+    // Stopping early when piped into e.g. `head` is not an error
+    match io::stdout().lock().write_all(out.as_bytes()) {
+        Err(e) if e.kind() != io::ErrorKind::BrokenPipe => Err(e.into()),
+        _ => Ok(()),
+    }
+}
+
+fn write_summary(out: &mut String, summary: &HistorySummary) -> std::fmt::Result {
+    write_overall(out, &summary.overall)?;
+
+    writeln!(out)?;
+    writeln!(out, "Best by mode and language")?;
+    writeln!(
+        out,
+        "  {:<8} {:<16} {:>6} {:>8} {:>9} {:>8}",
+        "MODE", "LANGUAGE", "TESTS", "AVG WPM", "BEST WPM", "AVG ACC"
+    )?;
+    for group in &summary.by_mode_language {
+        writeln!(
+            out,
+            "  {:<8} {:<16} {:>6} {:>8.1} {:>9.1} {:>7.1}%",
+            group.mode.label(),
+            group.language,
+            group.stats.total_tests,
+            group.stats.average_wpm,
+            group.stats.best_wpm,
+            group.stats.average_accuracy,
+        )?;
+    }
+
+    writeln!(out)?;
+    writeln!(out, "Last {} runs", summary.recent.len())?;
+    writeln!(
+        out,
+        "  {:<16} {:<8} {:<16} {:>7} {:>7} {:>7} {:>8}",
+        "DATE (UTC)", "MODE", "LANGUAGE", "WPM", "RAW", "ACC", "TIME"
+    )?;
+    for record in &summary.recent {
+        write_run(out, record)?;
+    }
+    Ok(())
+}
+
+fn write_overall(out: &mut String, stats: &UserStats) -> std::fmt::Result {
+    writeln!(out, "Overall")?;
+    writeln!(out, "  Tests:             {}", stats.total_tests)?;
+    if stats.imported_tests > 0 {
+        writeln!(out, "  Imported:          {}", stats.imported_tests)?;
+    }
+    writeln!(out, "  Average WPM:       {:.1}", stats.average_wpm)?;
+    writeln!(out, "  Best WPM:          {:.1}", stats.best_wpm)?;
+    writeln!(out, "  Average accuracy:  {:.1}%", stats.average_accuracy)?;
+    writeln!(out, "  Words typed:       {}", stats.total_words_typed)?;
+    writeln!(
+        out,
+        "  Time typing:       {}h {}m",
+        stats.total_time_seconds / 3600,
+        (stats.total_time_seconds % 3600) / 60
+    )
+}
+
+fn write_run(out: &mut String, record: &RunRecord) -> std::fmt::Result {
+    writeln!(
+        out,
+        "  {:<16} {:<8} {:<16} {:>7.1} {:>7.1} {:>6.1}% {:>7.1}s",
+        history::format_timestamp(record.timestamp),
+        record.mode.label(),
+        record.language,
+        record.wpm,
+        record.raw_wpm,
+        record.accuracy,
+        record.duration_secs,
+    )
+}
@@ -1,22 +1,55 @@
+use std::fs;
+use std::path::{Path, PathBuf};
 use super::models::Language;
+use super::transform::{self, TextOptions};
+use crate::app::paths;
+use rand::distr::Distribution;
+use rand::distr::weighted::WeightedIndex;
+use rand::SeedableRng;
 use rand::prelude::IndexedRandom;
+use rand_chacha::ChaCha8Rng;
+
+#[derive(Debug, Clone)]
+pub enum LoadStatus {
+    Loaded { name: String, words: usize },
+    Failed(String),
+}
+
+/// Outcome of loading one user language file.
+#[derive(Debug, Clone)]
+pub struct LoadReport {
+    pub path: PathBuf,
+    pub status: LoadStatus,
+}
 
 pub struct LanguageManager {
     languages: Vec<Language>,
+    reports: Vec<LoadReport>,
 }
 
 impl LanguageManager {
     pub fn new() -> Self {
-        let mut languages = Vec::new();
-        
-        if let Ok(lang) = Self::load_builtin("english_200") {
-            languages.push(lang);
-        }
-        if let Ok(lang) = Self::load_builtin("english_1k") {
-            languages.push(lang);
+        let mut manager = Self::builtin();
+        if let Some(dir) = paths::languages_dir() {
+            manager.load_dir(&dir);
         }
         
-        Self { languages }
+        manager
+    }
+
+    /// Loads only the languages compiled into the binary.
+    fn builtin() -> Self {
+        let mut manager = Self { languages: Vec::new(), reports: Vec::new() };
+        for name in ["english_200", "english_1k"] {
+            match Self::load_builtin(name) {
+                Ok(lang) => manager.languages.push(lang),
+                Err(err) => manager.reports.push(LoadReport {
+                    path: PathBuf::from(format!("<builtin>/{}.json", name)),
+                    status: LoadStatus::Failed(err.to_string()),
+                }),
+            }
+        }
+        manager
     }
     
     fn load_builtin(name: &str) -> Result<Language, Box<dyn std::error::Error>> {
@@ -29,6 +62,60 @@ impl LanguageManager {
         let language: Language = serde_json::from_str(json_data)?;
         Ok(language)
     }
+
+    /// Loads every `*.json` file in `dir`, recording a report for each one.
+    fn load_dir(&mut self, dir: &Path) {
+        let entries = match fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return,
+            Err(err) => {
+                self.reports.push(LoadReport {
+                    path: dir.to_path_buf(),
+                    status: LoadStatus::Failed(err.to_string()),
+                });
+                return;
+            }
+        };
+
+        let mut files: Vec<PathBuf> = entries
+            .filter_map(|e| e.ok().map(|e| e.path()))
+            .filter(|p| p.extension().is_some_and(|ext| ext == "json"))
+            .collect();
+        files.sort();
+
+        for path in files {
+            let status = match self.load_file(&path) {
+                Ok(language) => {
+                    let status = LoadStatus::Loaded {
+                        name: language.name.clone(),
+                        words: language.words.len(),
+                    };
+                    self.languages.push(language);
+                    status
+                }
+                Err(err) => LoadStatus::Failed(err.to_string()),
+            };
+            self.reports.push(LoadReport { path, status });
+        }
+    }
+
+    fn load_file(&self, path: &Path) -> Result<Language, Box<dyn std::error::Error>> {
+        let language: Language = serde_json::from_str(&fs::read_to_string(path)?)?;
+
+        if language.name.trim().is_empty() {
+            return Err("\"name\" is empty".into());
+        }
+        if self.get_language(&language.name).is_some() {
+            return Err(format!("a language named \"{}\" is already loaded", language.name).into());
+        }
+        if language.words.is_empty() {
+            return Err("\"words\" is empty".into());
+        }
+        if let Some(bad) = language.words.iter().find(|w| w.is_empty() || w.contains(char::is_whitespace)) {
+            return Err(format!("invalid word {:?}: words must be non-empty with no whitespace", bad).into());
+        }
+        Ok(language)
+    }
     
     pub fn get_language(&self, name: &str) -> Option<&Language> {
         self.languages.iter().find(|l| l.name == name)
@@ -37,10 +124,26 @@ impl LanguageManager {
     pub fn available_languages(&self) -> Vec<String> {
         self.languages.iter().map(|l| l.name.clone()).collect()
     }
+
+    /// Reports for user language files and any builtin that failed to load.
+    pub fn reports(&self) -> &[LoadReport] {
+        &self.reports
+    }
+
+    pub fn failed_count(&self) -> usize {
+        self.reports
+            .iter()
+            .filter(|r| matches!(r.status, LoadStatus::Failed(_)))
+            .count()
+    }
     
-    pub fn generate_text(&self, language_name: &str, word_count: usize) -> String {
+    /// Generates `word_count` random words. Within one remytype version the
+    /// same seed gives the same text for the same language, count and options;
+    /// a rand upgrade may change how words are picked, so seeds are not
+    /// guaranteed to carry across versions.
+    pub fn generate_text(&self, language_name: &str, word_count: usize, options: &TextOptions, seed: u64) -> String {
         if let Some(language) = self.get_language(language_name) {
-            let mut rng = rand::rng();
+            let mut rng = ChaCha8Rng::seed_from_u64(seed);
             let mut words: Vec<String> = Vec::new();
             
             for _ in 0..word_count {
@@ -49,9 +152,61 @@ impl LanguageManager {
                 }
             }
             
+            transform::apply(&mut words, options, &mut rng);
             words.join(" ")
         } else {
             "the quick brown fox jumps over the lazy dog".to_string()
         }
     }
+
+    /// Like `generate_text`, but picks each word with probability
+    /// proportional to `weight(word)`.
+    pub fn generate_weighted_text<F: Fn(&str) -> f64>(
+        &self,
+        language_name: &str,
+        word_count: usize,
+        options: &TextOptions,
+        seed: u64,
+        weight: F,
+    ) -> String {
+        let Some(language) = self.get_language(language_name) else {
+            return self.generate_text(language_name, word_count, options, seed);
+        };
+        let Ok(distribution) = WeightedIndex::new(language.words.iter().map(|w| weight(w))) else {
+            return self.generate_text(language_name, word_count, options, seed);
+        };
+
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        let mut words: Vec<String> = (0..word_count)
+            .map(|_| language.words[distribution.sample(&mut rng)].clone())
+            .collect();
+        transform::apply(&mut words, options, &mut rng);
+        words.join(" ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options() -> TextOptions {
+        TextOptions { punctuation: true, numbers: true, number_rate: 10 }
+    }
+
+    #[test]
+    fn same_seed_gives_same_text() {
+        let manager = LanguageManager::builtin();
+        let first = manager.generate_text("english_1k", 50, &options(), 42);
+        let second = manager.generate_text("english_1k", 50, &options(), 42);
+        assert_eq!(first, second);
+        assert_ne!(first, manager.generate_text("english_1k", 50, &options(), 43));
+    }
+
+    /// Pins the output, so a change that alters seeded text is noticed.
+    #[test]
+    fn seeded_text_is_stable() {
+        let manager = LanguageManager::builtin();
+        let text = manager.generate_text("english_200", 8, &TextOptions::default(), 7);
+        assert_eq!(text, "which all make there state begin he real");
+    }
 }
@@ -1,4 +1,6 @@
 mod manager;
 mod models;
+mod transform;
 
-pub use manager::LanguageManager;
+pub use manager::{LanguageManager, LoadStatus};
+pub use transform::TextOptions;
@@ -0,0 +1,79 @@
+use rand::Rng;
+
+/// Extra content mixed into the generated word stream.
+#[derive(Debug, Clone, Default)]
+pub struct TextOptions {
+    pub punctuation: bool,
+    pub numbers: bool,
+    /// Percentage of words replaced by numbers when `numbers` is on.
+    pub number_rate: usize,
+}
+
+/// Rewrites a plain list of words according to `options`.
+pub fn apply<R: Rng>(words: &mut [String], options: &TextOptions, rng: &mut R) {
+    if options.numbers {
+        let rate = options.number_rate.min(100) as f64 / 100.0;
+        for word in words.iter_mut() {
+            if rng.random_bool(rate) {
+                *word = random_number(rng);
+            }
+        }
+    }
+    if options.punctuation {
+        punctuate(words, rng);
+    }
+}
+
+fn random_number<R: Rng>(rng: &mut R) -> String {
+    let digits = rng.random_range(1..=4u32);
+    rng.random_range(0..10u32.pow(digits)).to_string()
+}
+
+/// Turns the words into sentence-like text: capitalised sentence starts,
+/// trailing punctuation, and the occasional quoted or bracketed word.
+fn punctuate<R: Rng>(words: &mut [String], rng: &mut R) {
+    let last = words.len().saturating_sub(1);
+    let mut sentence_start = true;
+
+    for (i, word) in words.iter_mut().enumerate() {
+        if sentence_start {
+            *word = capitalise(word);
+        }
+
+        let roll = rng.random_range(0..100);
+        if roll < 3 {
+            *word = format!("\"{}\"", word);
+        } else if roll < 5 {
+            *word = format!("({})", word);
+        }
+
+        sentence_start = false;
+        if i == last {
+            word.push('.');
+            continue;
+        }
+        let roll = rng.random_range(0..100);
+        if roll < 8 {
+            word.push('.');
+            sentence_start = true;
+        } else if roll < 10 {
+            word.push('?');
+            sentence_start = true;
+        } else if roll < 11 {
+            word.push('!');
+            sentence_start = true;
+        } else if roll < 20 {
+            word.push(',');
+        } else if roll < 22 {
+            word.push(';');
+        }
+    }
+}
+
+fn capitalise(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
@@ -1,8 +1,15 @@
 mod app;
+mod cli;
+mod commands;
 mod ui;
+mod code;
 mod languages;
+mod quotes;
+mod themes;
 
 use std::io;
+use std::process;
+use std::time::Duration;
 use crossterm::{
     event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode},
     execute,
@@ -13,8 +20,43 @@ use ratatui::{
     Terminal,
 };
 use app::{App, AppMode};
+use app::export::ExportFormat;
+use app::state::StatsView;
+use cli::Command;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let run_args = match cli::parse(std::env::args().skip(1)) {
+        Ok(Command::Run(args)) => args,
+        Ok(Command::Help) => {
+            println!("{}", cli::USAGE);
+            return Ok(());
+        }
+        Ok(Command::Stats(args)) => exit_with(commands::stats(&args)),
+        Ok(Command::Export(args)) => exit_with(commands::export(&args)),
+        Ok(Command::Import(args)) => exit_with(commands::import(&args)),
+        Ok(Command::Version) => {
+            println!("remytype {}", env!("CARGO_PKG_VERSION"));
+            return Ok(());
+        }
+        Err(err) => {
+            eprintln!("remytype: {}\n\nRun 'remytype --help' for usage.", err);
+            process::exit(2);
+        }
+    };
+
+    // Create app, reading any custom text before the terminal switches to raw mode
+    let mut app = App::new();
+    let start_test = match run_args.apply(&mut app) {
+        Ok(start_test) => start_test,
+        Err(err) => {
+            eprintln!("remytype: {}", err);
+            process::exit(2);
+        }
+    };
+    if start_test {
+        app.start_test();
+    }
+
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -22,8 +64,6 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    // Create app
-    let app = App::new();
     let res = run_app(&mut terminal, app);
 
     // Restore terminal
@@ -42,10 +82,30 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+/// Ends a headless subcommand, reporting its error if it failed.
+fn exit_with(result: Result<(), Box<dyn std::error::Error>>) -> ! {
+    match result {
+        Ok(()) => process::exit(0),
+        Err(err) => {
+            eprintln!("remytype: {}", err);
+            process::exit(1);
+        }
+    }
+}
+
+/// How often the screen redraws while waiting for input, so timers stay live.
+const TICK_RATE: Duration = Duration::from_millis(100);
+
 fn run_app(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, mut app: App) -> io::Result<()> {
     loop {
         terminal.draw(|f| ui::render(f, &app))?;
 
+        let has_event = event::poll(TICK_RATE)?;
+        app.tick();
+        if !has_event {
+            continue;
+        }
+
         if let Event::Key(key) = event::read()? {
             match app.mode {
                 AppMode::Menu => {
@@ -56,6 +116,7 @@ fn run_app(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, mut app: App)
                                 0 => app.start_test(),
                                 1 => app.open_stats(),
                                 2 => app.open_settings(),
+                                3 => app.open_diagnostics(),
                                 _ => {}
                             }
                         }
@@ -68,6 +129,7 @@ fn run_app(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, mut app: App)
                     match key.code {
                         KeyCode::Esc => app.return_to_menu(),
                         KeyCode::Char(c) => app.type_char(c),
+                        KeyCode::Enter => app.type_char('\n'),
                         KeyCode::Backspace => app.backspace(),
                         _ => {}
                     }
@@ -75,10 +137,40 @@ fn run_app(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, mut app: App)
                 AppMode::Results => {
                     match key.code {
                         KeyCode::Esc | KeyCode::Enter => app.return_to_menu(),
+                        KeyCode::Char('r') => app.start_replay(app.history.len().saturating_sub(1)),
                         _ => {}
                     }
                 }
                 AppMode::Stats => {
+                    match key.code {
+                        KeyCode::Esc => app.stats_back(),
+                        KeyCode::Enter => app.stats_select(),
+                        KeyCode::Up => app.stats_up(),
+                        KeyCode::Down => app.stats_down(),
+                        KeyCode::Tab => app.next_stats_view(),
+                        KeyCode::Char('n') => app.toggle_ngram_size(),
+                        KeyCode::Char('g') if app.stats_view == StatsView::Runs => {
+                            if let Some(&run) = app.runs_by_date().get(app.run_selection) {
+                                app.start_race(run);
+                            }
+                        }
+                        KeyCode::Char('m') => app.toggle_key_metric(),
+                        KeyCode::Char('c') => app.export_history(ExportFormat::Csv),
+                        KeyCode::Char('j') => app.export_history(ExportFormat::Json),
+                        _ => {}
+                    }
+                }
+                AppMode::Replay => {
+                    match (key.code, app.replay.as_mut()) {
+                        (KeyCode::Esc | KeyCode::Enter, _) => app.close_replay(),
+                        (KeyCode::Char(' '), Some(replay)) => replay.toggle_pause(),
+                        (KeyCode::Char('r'), Some(replay)) => replay.restart(),
+                        (KeyCode::Left, Some(replay)) => replay.change_speed(false),
+                        (KeyCode::Right, Some(replay)) => replay.change_speed(true),
+                        _ => {}
+                    }
+                }
+                AppMode::Diagnostics => {
                     match key.code {
                         KeyCode::Esc | KeyCode::Enter => app.return_to_menu(),
                         _ => {}
@@ -86,12 +178,12 @@ fn run_app(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, mut app: App)
                 }
                 AppMode::Settings => {
                     match key.code {
-                        KeyCode::Esc => app.return_to_menu(),
+                        KeyCode::Esc => app.close_settings(),
                         KeyCode::Up => app.settings_up(),
                         KeyCode::Down => app.settings_down(),
                         KeyCode::Left => app.modify_setting(false),
                         KeyCode::Right => app.modify_setting(true),
-                        KeyCode::Enter => app.return_to_menu(),
+                        KeyCode::Enter => app.close_settings(),
                         _ => {}
                     }
                 }
@@ -0,0 +1,38 @@
+use super::models::{Quote, QuoteCollection, QuoteLength};
+use rand::prelude::IndexedRandom;
+
+pub struct QuoteManager {
+    quotes: Vec<Quote>,
+}
+
+impl QuoteManager {
+    pub fn new() -> Self {
+        let quotes = Self::load_builtin().map(|c| c.quotes).unwrap_or_default();
+        Self { quotes }
+    }
+
+    fn load_builtin() -> Result<QuoteCollection, Box<dyn std::error::Error>> {
+        let json_data = include_str!("../../data/quotes/english.json");
+        let collection: QuoteCollection = serde_json::from_str(json_data)?;
+        Ok(collection)
+    }
+
+    pub fn get(&self, id: usize) -> Option<&Quote> {
+        self.quotes.iter().find(|q| q.id == id)
+    }
+
+    /// Picks a random quote of the given length, falling back to any quote
+    /// when the bucket is empty.
+    pub fn random_quote(&self, length: QuoteLength) -> Option<&Quote> {
+        let mut rng = rand::rng();
+        let matching: Vec<&Quote> = self
+            .quotes
+            .iter()
+            .filter(|q| QuoteLength::of(&q.text) == length)
+            .collect();
+        match matching.choose(&mut rng) {
+            Some(quote) => Some(quote),
+            None => self.quotes.choose(&mut rng),
+        }
+    }
+}
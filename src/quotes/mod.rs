@@ -0,0 +1,5 @@
+mod manager;
+mod models;
+
+pub use manager::QuoteManager;
+pub use models::{Quote, QuoteLength};
@@ -0,0 +1,46 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Quote {
+    pub id: usize,
+    pub text: String,
+    pub source: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuoteCollection {
+    pub language: String,
+    pub quotes: Vec<Quote>,
+}
+
+/// Length buckets for quote mode, by character count.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum QuoteLength {
+    Short,
+    Medium,
+    Long,
+    Thicc,
+}
+
+impl QuoteLength {
+    pub const ALL: [QuoteLength; 4] = [Self::Short, Self::Medium, Self::Long, Self::Thicc];
+
+    pub fn of(text: &str) -> Self {
+        match text.chars().count() {
+            0..=100 => Self::Short,
+            101..=300 => Self::Medium,
+            301..=600 => Self::Long,
+            _ => Self::Thicc,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Short => "short",
+            Self::Medium => "medium",
+            Self::Long => "long",
+            Self::Thicc => "thicc",
+        }
+    }
+}
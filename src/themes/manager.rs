@@ -0,0 +1,86 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use super::models::{Theme, ThemeFile};
+use crate::app::{config, paths};
+
+pub struct ThemeManager {
+    themes: Vec<Theme>,
+    /// One message per theme file that could not be loaded.
+    failures: Vec<String>,
+}
+
+impl ThemeManager {
+    pub fn new() -> Self {
+        let mut manager = Self { themes: vec![Theme::default()], failures: Vec::new() };
+
+        let builtins = [
+            ("high-contrast.toml", include_str!("../../data/themes/high-contrast.toml")),
+            ("colour-blind.toml", include_str!("../../data/themes/colour-blind.toml")),
+            ("solarized-dark.toml", include_str!("../../data/themes/solarized-dark.toml")),
+            ("paper.toml", include_str!("../../data/themes/paper.toml")),
+        ];
+        for (file, contents) in builtins {
+            manager.add(Path::new(file), Ok(contents.to_string()));
+        }
+        if let Some(dir) = paths::themes_dir() {
+            manager.load_dir(&dir);
+        }
+
+        manager
+    }
+
+    /// Loads every `*.toml` file in `dir`.
+    fn load_dir(&mut self, dir: &Path) {
+        let entries = match fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return,
+            Err(err) => {
+                self.failures.push(format!("{} ({})", dir.display(), err));
+                return;
+            }
+        };
+
+        let mut files: Vec<PathBuf> = entries
+            .filter_map(|e| e.ok().map(|e| e.path()))
+            .filter(|p| p.extension().is_some_and(|ext| ext == "toml"))
+            .collect();
+        files.sort();
+
+        for path in files {
+            let contents = fs::read_to_string(&path).map_err(|e| e.to_string());
+            self.add(&path, contents);
+        }
+    }
+
+    fn add(&mut self, path: &Path, contents: Result<String, String>) {
+        let result = contents.and_then(|contents| {
+            toml::from_str::<ThemeFile>(&contents)
+                .map_err(|e| config::describe_toml_error(&e, &contents))
+                .and_then(ThemeFile::into_theme)
+        });
+        match result {
+            Ok(theme) if self.get(&theme.name).is_some() => self
+                .failures
+                .push(format!("{} (a theme named \"{}\" is already loaded)", path.display(), theme.name)),
+            Ok(theme) => self.themes.push(theme),
+            Err(reason) => self.failures.push(format!("{} ({})", path.display(), reason)),
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Theme> {
+        self.themes.iter().find(|t| t.name == name)
+    }
+
+    /// The theme called `name`, or the default theme if there is none.
+    pub fn get_or_default(&self, name: &str) -> &Theme {
+        self.get(name).unwrap_or(&self.themes[0])
+    }
+
+    pub fn available_themes(&self) -> Vec<String> {
+        self.themes.iter().map(|t| t.name.clone()).collect()
+    }
+
+    pub fn failures(&self) -> &[String] {
+        &self.failures
+    }
+}
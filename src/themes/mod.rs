@@ -0,0 +1,5 @@
+mod manager;
+mod models;
+
+pub use manager::ThemeManager;
+pub use models::Theme;
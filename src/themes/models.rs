@@ -0,0 +1,88 @@
+use std::str::FromStr;
+use ratatui::style::Color;
+use serde::Deserialize;
+
+/// Colours for every part of the interface.
+#[derive(Debug, Clone)]
+pub struct Theme {
+    pub name: String,
+    pub background: Color,
+    /// Ordinary text, and the default for anything not styled otherwise.
+    pub text: Color,
+    pub caret: Color,
+    /// Correctly typed characters, and good results elsewhere.
+    pub correct: Color,
+    /// Mistakes, and bad results elsewhere.
+    pub error: Color,
+    /// Letters typed past the end of a word.
+    pub extra: Color,
+    /// Titles, labels and the selected item.
+    pub accent: Color,
+    /// Untyped text, hints and secondary details.
+    pub dim: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            name: "default".to_string(),
+            background: Color::Reset,
+            text: Color::White,
+            caret: Color::White,
+            correct: Color::Green,
+            error: Color::Red,
+            extra: Color::LightRed,
+            accent: Color::Cyan,
+            dim: Color::Gray,
+        }
+    }
+}
+
+impl Theme {
+    /// Four steps from good to bad, for heatmaps.
+    pub fn scale(&self) -> [Color; 4] {
+        [self.correct, self.accent, self.extra, self.error]
+    }
+}
+
+/// A theme as written in a TOML file. Colours are names ("light-red"),
+/// hex ("#ff8800") or 256-colour indices ("208"); any left out are taken
+/// from the default theme.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ThemeFile {
+    name: String,
+    background: Option<String>,
+    text: Option<String>,
+    caret: Option<String>,
+    correct: Option<String>,
+    error: Option<String>,
+    extra: Option<String>,
+    accent: Option<String>,
+    dim: Option<String>,
+}
+
+impl ThemeFile {
+    pub fn into_theme(self) -> Result<Theme, String> {
+        let name = self.name.trim().to_string();
+        if name.is_empty() {
+            return Err("\"name\" is empty".to_string());
+        }
+        let base = Theme::default();
+        let colour = |key: &str, value: Option<String>, fallback: Color| match value {
+            Some(value) => Color::from_str(&value).map_err(|_| format!("{}: unknown colour {:?}", key, value)),
+            None => Ok(fallback),
+        };
+        Ok(Theme {
+            name,
+            background: colour("background", self.background, base.background)?,
+            text: colour("text", self.text, base.text)?,
+            caret: colour("caret", self.caret, base.caret)?,
+            correct: colour("correct", self.correct, base.correct)?,
+            error: colour("error", self.error, base.error)?,
+            extra: colour("extra", self.extra, base.extra)?,
+            accent: colour("accent", self.accent, base.accent)?,
+            dim: colour("dim", self.dim, base.dim)?,
+        })
+    }
+}
@@ -1,14 +1,16 @@
 use ratatui::{
     layout::{Alignment, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     widgets::{Block, Borders, Paragraph},
     Frame,
 };
 use crate::app::{App, AppMode};
+use crate::app::state::StatsView;
+use crate::themes::Theme;
 
-pub fn render_header(f: &mut Frame, area: Rect) {
+pub fn render_header(f: &mut Frame, theme: &Theme, area: Rect) {
     let title = Paragraph::new("RemyType")
-        .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
+        .style(Style::default().fg(theme.accent).add_modifier(Modifier::BOLD))
         .alignment(Alignment::Center)
         .block(Block::default().borders(Borders::ALL));
     f.render_widget(title, area);
@@ -18,14 +20,38 @@ pub fn render_footer(f: &mut Frame, app: &App, area: Rect) {
     let help_text = match app.mode {
         AppMode::Menu => "[↑/↓] Navigate | [Enter] Select | [q] Quit",
         AppMode::Test => "[Esc] Cancel | Type to test your speed!",
-        AppMode::Results => "[Enter/Esc] Return to menu",
-        AppMode::Stats => "[Enter/Esc] Return to menu",
+        AppMode::Results => "[r] Replay | [Enter/Esc] Return to menu",
+        AppMode::Stats if app.ngram_trend.is_some() => "[Enter/Esc] Back to list",
+        AppMode::Stats if app.stats_view == StatsView::Runs => {
+            "[↑/↓] Select | [Enter] Replay | [g] Race ghost | [Tab] View | [Esc] Menu"
+        }
+        AppMode::Stats if app.stats_view == StatsView::Ngrams => {
+            "[↑/↓] Select | [Enter] Trend | [n] Size | [m] Sort | [Tab] View | [Esc] Menu"
+        }
+        AppMode::Stats => "[Tab] View | [m] Metric | [c/j] Export CSV/JSON | [Enter/Esc] Menu",
         AppMode::Settings => "[↑/↓] Navigate | [←/→] Adjust | [Enter/Esc] Back to menu",
+        AppMode::Diagnostics => "[Enter/Esc] Return to menu",
+        AppMode::Replay => "[Space] Pause | [←/→] Speed | [r] Restart | [Enter/Esc] Back",
     };
 
     let footer = Paragraph::new(help_text)
-        .style(Style::default().fg(Color::Gray))
+        .style(Style::default().fg(app.theme().dim))
         .alignment(Alignment::Center)
         .block(Block::default().borders(Borders::ALL));
     f.render_widget(footer, area);
 }
+
+/// Returns the first row and row count of a list drawn in `area` below a one
+/// line header, scrolled so `selection` stays on screen.
+pub fn list_window(area: Rect, selection: usize) -> (usize, usize) {
+    let rows = (area.height as usize).saturating_sub(1).max(1);
+    (selection.saturating_sub(rows - 1), rows)
+}
+
+/// Appends the Stats screen's status message, if any, to a block title.
+pub fn stats_title(app: &App, title: &str) -> String {
+    match &app.stats_status {
+        Some(status) => format!("{} · {}", title, status),
+        None => title.to_string(),
+    }
+}
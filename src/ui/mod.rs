@@ -4,14 +4,26 @@ mod render_test;
 mod render_stats;
 mod render_settings;
 mod render_user_stats;
+mod render_keyboard;
+mod render_ngrams;
+mod render_runs;
+mod render_diagnostics;
+mod text_layout;
 
 use ratatui::{
     layout::{Constraint, Direction, Layout},
+    style::Style,
+    widgets::Block,
     Frame,
 };
 use crate::app::{App, AppMode};
 
 pub fn render(f: &mut Frame, app: &App) {
+    // Everything is drawn over the theme's background and text colour
+    let theme = app.theme();
+    let base = Style::default().fg(theme.text).bg(theme.background);
+    f.render_widget(Block::default().style(base), f.area());
+
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .margin(2)
@@ -22,14 +34,15 @@ pub fn render(f: &mut Frame, app: &App) {
         ])
         .split(f.area());
 
-    layout::render_header(f, chunks[0]);
+    layout::render_header(f, theme, chunks[0]);
 
     match app.mode {
         AppMode::Menu => render_menu::render(f, app, chunks[1]),
-        AppMode::Test => render_test::render(f, app, chunks[1]),
+        AppMode::Test | AppMode::Replay => render_test::render(f, app, chunks[1]),
         AppMode::Results => render_stats::render(f, app, chunks[1]),
         AppMode::Stats => render_user_stats::render(f, app, chunks[1]),
         AppMode::Settings => render_settings::render(f, app, chunks[1]),
+        AppMode::Diagnostics => render_diagnostics::render(f, app, chunks[1]),
     }
 
     layout::render_footer(f, app, chunks[2]);
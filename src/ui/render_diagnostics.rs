@@ -0,0 +1,66 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph},
+    Frame,
+};
+use crate::app::{paths, App};
+use crate::languages::LoadStatus;
+
+pub fn render(f: &mut Frame, app: &App, area: Rect) {
+    let theme = app.theme();
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(0),
+        ])
+        .split(area);
+
+    let dir = paths::languages_dir()
+        .map(|d| d.display().to_string())
+        .unwrap_or_else(|| "(no data directory)".to_string());
+    let title = Paragraph::new(format!("Language files in {}", dir))
+        .style(Style::default().fg(theme.accent).add_modifier(Modifier::BOLD))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL));
+    f.render_widget(title, chunks[0]);
+
+    let reports = app.language_manager.reports();
+    let items: Vec<ListItem> = if reports.is_empty() {
+        vec![ListItem::new(Line::from(Span::styled(
+            "No user language files found. Add *.json files with \"name\" and \"words\" to load them.",
+            Style::default().fg(theme.dim),
+        )))]
+    } else {
+        reports
+            .iter()
+            .map(|report| {
+                let file = report
+                    .path
+                    .file_name()
+                    .map(|n| n.to_string_lossy().into_owned())
+                    .unwrap_or_else(|| report.path.display().to_string());
+                let content = match &report.status {
+                    LoadStatus::Loaded { name, words } => Line::from(vec![
+                        Span::styled("✓ ", Style::default().fg(theme.correct).add_modifier(Modifier::BOLD)),
+                        Span::styled(file, Style::default().fg(theme.text)),
+                        Span::styled(format!(": loaded \"{}\" ({} words)", name, words), Style::default().fg(theme.dim)),
+                    ]),
+                    LoadStatus::Failed(reason) => Line::from(vec![
+                        Span::styled("✗ ", Style::default().fg(theme.error).add_modifier(Modifier::BOLD)),
+                        Span::styled(file, Style::default().fg(theme.text)),
+                        Span::styled(format!(": {}", reason), Style::default().fg(theme.error)),
+                    ]),
+                };
+                ListItem::new(content)
+            })
+            .collect()
+    };
+
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title("Diagnostics"))
+        .style(Style::default().fg(theme.text));
+    f.render_widget(list, chunks[1]);
+}
@@ -0,0 +1,182 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+use crate::app::App;
+use crate::app::keystats::{KeyStats, MIN_ATTEMPTS};
+use crate::app::state::KeyMetric;
+use crate::themes::Theme;
+use super::layout;
+
+/// US QWERTY rows as (indent, keys). '\n' is Enter and ' ' the space bar.
+const ROWS: [(usize, &str); 5] = [
+    (0, "`1234567890-="),
+    (3, "qwertyuiop[]\\"),
+    (4, "asdfghjkl;'\n"),
+    (6, "zxcvbnm,./"),
+    (18, " "),
+];
+
+/// Columns per key, including the gap to the next one.
+const KEY_WIDTH: usize = 5;
+const SPACE_WIDTH: usize = 29;
+/// Width of the widest row, the number row plus the Tab indent below it.
+const KEYBOARD_WIDTH: usize = 3 + 13 * KEY_WIDTH;
+
+pub fn render(f: &mut Frame, app: &App, area: Rect) {
+    let theme = app.theme();
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(10),
+            Constraint::Min(0),
+        ])
+        .split(area);
+
+    let title = layout::stats_title(app, match app.key_metric {
+        KeyMetric::ErrorRate => "Error rate by key",
+        KeyMetric::Latency => "Speed by key",
+    });
+
+    if app.key_stats.is_empty() {
+        let empty = Paragraph::new(vec![
+            Line::from(""),
+            Line::from("No keystroke data yet. Finish a test to start building your heatmap."),
+        ])
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL).title(title.as_str()));
+        f.render_widget(empty, area);
+        return;
+    }
+
+    // Rows are left-aligned so the stagger survives, inside a centred margin
+    let margin = (chunks[0].width as usize).saturating_sub(2 + KEYBOARD_WIDTH) / 2;
+    let scale = Scale::new(app, theme);
+    let mut lines = vec![Line::from("")];
+    for (indent, keys) in ROWS {
+        let mut spans = vec![Span::raw(" ".repeat(margin + indent))];
+        for key in keys.chars() {
+            let stats = app.key_stats.get(&key);
+            let width = if key == ' ' { SPACE_WIDTH } else { KEY_WIDTH - 1 };
+            let style = match stats.filter(|s| s.attempts > 0) {
+                Some(stats) => Style::default().fg(scale.colour(stats)).add_modifier(Modifier::REVERSED),
+                None => Style::default().fg(theme.dim).add_modifier(Modifier::REVERSED),
+            };
+            spans.push(Span::styled(format!("{:^width$}", key_label(key), width = width), style));
+            spans.push(Span::raw(" "));
+        }
+        lines.push(Line::from(spans));
+    }
+    lines.push(Line::from(""));
+    lines.push(legend(theme, app.key_metric).alignment(Alignment::Center));
+
+    let keyboard = Paragraph::new(lines)
+        .block(Block::default().borders(Borders::ALL).title(title.as_str()));
+    f.render_widget(keyboard, chunks[0]);
+
+    render_weakest(f, app, chunks[1]);
+}
+
+/// Maps a key's statistic onto the theme's four step good-to-bad scale.
+struct Scale {
+    colours: [Color; 4],
+    no_data: Color,
+    metric: KeyMetric,
+    /// Average latency over all keys, so speed is coloured relative to the user.
+    average_latency: f64,
+}
+
+impl Scale {
+    fn new(app: &App, theme: &Theme) -> Self {
+        let (total, timed) = app
+            .key_stats
+            .values()
+            .fold((0, 0), |(total, timed), s| (total + s.total_latency_ms, timed + s.timed));
+        Self {
+            colours: theme.scale(),
+            no_data: theme.dim,
+            metric: app.key_metric,
+            average_latency: if timed > 0 { total as f64 / timed as f64 } else { 0.0 },
+        }
+    }
+
+    fn colour(&self, stats: &KeyStats) -> Color {
+        let level = match self.metric {
+            KeyMetric::ErrorRate => {
+                let rate = stats.error_rate();
+                [2.0, 5.0, 10.0].iter().filter(|&&limit| rate >= limit).count()
+            }
+            KeyMetric::Latency => match stats.average_latency() {
+                Some(latency) if self.average_latency > 0.0 => {
+                    let ratio = latency / self.average_latency;
+                    [0.9, 1.1, 1.3].iter().filter(|&&limit| ratio >= limit).count()
+                }
+                _ => return self.no_data,
+            },
+        };
+        self.colours[level]
+    }
+}
+
+fn legend(theme: &Theme, metric: KeyMetric) -> Line<'static> {
+    let labels = match metric {
+        KeyMetric::ErrorRate => ["< 2%", "2-5%", "5-10%", "≥ 10%"],
+        KeyMetric::Latency => ["fast", "average", "slow", "slowest"],
+    };
+    let mut spans = Vec::new();
+    for (label, colour) in labels.into_iter().zip(theme.scale()) {
+        spans.push(Span::styled("  ", Style::default().bg(colour)));
+        spans.push(Span::raw(format!(" {}   ", label)));
+    }
+    spans.push(Span::styled("  ", Style::default().bg(theme.dim)));
+    spans.push(Span::raw(" no data"));
+    Line::from(spans)
+}
+
+/// Lists the keys that most need practice under the current metric.
+fn render_weakest(f: &mut Frame, app: &App, area: Rect) {
+    let theme = app.theme();
+    let mut keys: Vec<(char, &KeyStats)> = app
+        .key_stats
+        .iter()
+        .filter(|(_, s)| s.attempts >= MIN_ATTEMPTS)
+        .map(|(&k, s)| (k, s))
+        .collect();
+    let value = |s: &KeyStats| match app.key_metric {
+        KeyMetric::ErrorRate => s.error_rate(),
+        KeyMetric::Latency => s.average_latency().unwrap_or(0.0),
+    };
+    keys.sort_by(|a, b| value(b.1).total_cmp(&value(a.1)).then(a.0.cmp(&b.0)));
+
+    let mut spans = Vec::new();
+    for (key, stats) in keys.iter().take(8) {
+        let figure = match app.key_metric {
+            KeyMetric::ErrorRate => format!("{:.1}%", stats.error_rate()),
+            KeyMetric::Latency => format!("{:.0}ms", stats.average_latency().unwrap_or(0.0)),
+        };
+        spans.push(Span::styled(
+            key_label(*key),
+            Style::default().fg(theme.accent).add_modifier(Modifier::BOLD),
+        ));
+        spans.push(Span::raw(format!(" {} ({}×)   ", figure, stats.attempts)));
+    }
+    if spans.is_empty() {
+        spans.push(Span::styled("Not enough data yet", Style::default().fg(theme.dim)));
+    }
+
+    let weakest = Paragraph::new(vec![Line::from(""), Line::from(spans)])
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL).title("Weakest keys"));
+    f.render_widget(weakest, area);
+}
+
+fn key_label(key: char) -> String {
+    match key {
+        ' ' => "space".to_string(),
+        '\n' => "⏎".to_string(),
+        other => other.to_string(),
+    }
+}
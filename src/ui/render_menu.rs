@@ -1,6 +1,6 @@
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, List, ListItem, Paragraph},
     Frame,
@@ -8,6 +8,7 @@ use ratatui::{
 use crate::app::App;
 
 pub fn render(f: &mut Frame, app: &App, area: Rect) {
+    let theme = app.theme();
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -16,24 +17,32 @@ pub fn render(f: &mut Frame, app: &App, area: Rect) {
         ])
         .split(area);
 
-    // Welcome message
+    // Welcome message, replaced by the warning line when there is one to show
+    let notice = match &app.warning {
+        Some(warning) => Line::from(Span::styled(
+            format!("⚠ {}", warning),
+            Style::default().fg(theme.error),
+        )),
+        None => Line::from(""),
+    };
     let welcome = Paragraph::new(vec![
         Line::from(""),
         Line::from(Span::styled(
             "Welcome to Typing Tester!",
-            Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+            Style::default().fg(theme.accent).add_modifier(Modifier::BOLD),
         )),
-        Line::from(""),
+        notice,
     ])
     .alignment(Alignment::Center)
     .block(Block::default().borders(Borders::ALL).title("Welcome"));
     f.render_widget(welcome, chunks[0]);
 
     // Menu options
-    let menu_items = vec![
+    let menu_items = [
         "Start Test",
         "View Statistics",
         "Settings",
+        "Diagnostics",
     ];
 
     let items: Vec<ListItem> = menu_items
@@ -42,13 +51,13 @@ pub fn render(f: &mut Frame, app: &App, area: Rect) {
         .map(|(i, item)| {
             let content = if i == app.menu_selection {
                 Line::from(vec![
-                    Span::styled("→ ", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
-                    Span::styled(*item, Style::default().fg(Color::White).add_modifier(Modifier::BOLD)),
+                    Span::styled("→ ", Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)),
+                    Span::styled(*item, Style::default().fg(theme.text).add_modifier(Modifier::BOLD)),
                 ])
             } else {
                 Line::from(vec![
                     Span::raw("  "),
-                    Span::styled(*item, Style::default().fg(Color::Gray)),
+                    Span::styled(*item, Style::default().fg(theme.dim)),
                 ])
             };
             ListItem::new(content)
@@ -57,7 +66,7 @@ pub fn render(f: &mut Frame, app: &App, area: Rect) {
 
     let list = List::new(items)
         .block(Block::default().borders(Borders::ALL).title("Menu"))
-        .style(Style::default().fg(Color::White));
+        .style(Style::default().fg(theme.text));
 
     f.render_widget(list, chunks[1]);
 }
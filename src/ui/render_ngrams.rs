@@ -0,0 +1,187 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    symbols::Marker,
+    text::{Line, Span},
+    widgets::{Axis, Block, Borders, Chart, Dataset, GraphType, Paragraph},
+    Frame,
+};
+use crate::app::App;
+use crate::app::history;
+use super::layout;
+use crate::app::keystats::KeyStats;
+use crate::app::state::KeyMetric;
+
+pub fn render(f: &mut Frame, app: &App, area: Rect) {
+    match &app.ngram_trend {
+        Some((ngram, trend)) => render_trend(f, app, ngram, trend, area),
+        None => render_list(f, app, area),
+    }
+}
+
+fn size_name(size: usize) -> &'static str {
+    if size == 3 { "trigrams" } else { "bigrams" }
+}
+
+fn figure(metric: KeyMetric, stats: &KeyStats) -> f64 {
+    match metric {
+        KeyMetric::ErrorRate => stats.error_rate(),
+        KeyMetric::Latency => stats.average_latency().unwrap_or(0.0),
+    }
+}
+
+fn render_list(f: &mut Frame, app: &App, area: Rect) {
+    let theme = app.theme();
+    let title = match app.key_metric {
+        KeyMetric::ErrorRate => format!("Most error-prone {}", size_name(app.ngram_size)),
+        KeyMetric::Latency => format!("Slowest {}", size_name(app.ngram_size)),
+    };
+    let block = Block::default().borders(Borders::ALL).title(layout::stats_title(app, &title));
+
+    if app.ngram_stats.is_empty() {
+        let empty = Paragraph::new(vec![
+            Line::from(""),
+            Line::from("Not enough keystroke data yet. Finish a few tests to rank your n-grams."),
+        ])
+        .alignment(Alignment::Center)
+        .block(block);
+        f.render_widget(empty, area);
+        return;
+    }
+
+    let (first, rows) = layout::list_window(block.inner(area), app.ngram_selection);
+
+    let header = Style::default().fg(theme.accent).add_modifier(Modifier::BOLD);
+    let mut lines = vec![Line::from(Span::styled(
+        format!("    {:<3} {:<8} {:>10} {:>8} {:>9}", "#", "N-GRAM", "LATENCY", "ERRORS", "ATTEMPTS"),
+        header,
+    ))];
+    for (i, (ngram, stats)) in app.ngram_stats.iter().enumerate().skip(first).take(rows) {
+        let selected = i == app.ngram_selection;
+        let row = format!(
+            "{:<3} {:<8} {:>7.0} ms {:>7.1}% {:>9}",
+            i + 1,
+            format!("\"{}\"", ngram),
+            stats.average_latency().unwrap_or(0.0),
+            stats.error_rate(),
+            stats.attempts,
+        );
+        lines.push(if selected {
+            Line::from(vec![
+                Span::styled("  → ", Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)),
+                Span::styled(row, Style::default().fg(theme.text).add_modifier(Modifier::BOLD)),
+            ])
+        } else {
+            Line::from(vec![Span::raw("    "), Span::styled(row, Style::default().fg(theme.dim))])
+        });
+    }
+
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/// Shows how one n-gram's speed or accuracy changed from run to run.
+fn render_trend(f: &mut Frame, app: &App, ngram: &str, trend: &[(u64, KeyStats)], area: Rect) {
+    let theme = app.theme();
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(4),
+            Constraint::Min(6),
+        ])
+        .split(area);
+
+    let metric = app.key_metric;
+    // Runs where every attempt followed a pause have no latency to plot
+    let trend: Vec<(u64, KeyStats)> = trend
+        .iter()
+        .filter(|(_, s)| metric == KeyMetric::ErrorRate || s.timed > 0)
+        .cloned()
+        .collect();
+    let points: Vec<(f64, f64)> = trend
+        .iter()
+        .enumerate()
+        .map(|(i, (_, s))| ((i + 1) as f64, figure(metric, s)))
+        .collect();
+
+    // Compare the first and last few runs, weighting each run by its attempts
+    let window = (trend.len() / 3).clamp(1, 10);
+    let combined = |runs: &[(u64, KeyStats)]| {
+        let mut total = KeyStats::default();
+        for (_, s) in runs {
+            total.attempts += s.attempts;
+            total.errors += s.errors;
+            total.total_latency_ms += s.total_latency_ms;
+            total.timed += s.timed;
+        }
+        figure(metric, &total)
+    };
+    let unit = match metric {
+        KeyMetric::ErrorRate => "%",
+        KeyMetric::Latency => " ms",
+    };
+    let first = combined(&trend[..window.min(trend.len())]);
+    let last = combined(&trend[trend.len().saturating_sub(window)..]);
+    let change = match metric {
+        KeyMetric::ErrorRate => format!("{:.1}{} → {:.1}{}", first, unit, last, unit),
+        KeyMetric::Latency => format!("{:.0}{} → {:.0}{}", first, unit, last, unit),
+    };
+    let improved = last <= first;
+
+    let summary = Paragraph::new(vec![
+        Line::from(vec![
+            Span::styled(format!("\"{}\"", ngram), Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)),
+            Span::styled(format!(" in {} run(s)", trend.len()), Style::default().fg(theme.dim)),
+        ]),
+        Line::from(vec![
+            Span::styled(format!("First {} vs last {} runs: ", window, window), Style::default().fg(theme.dim)),
+            Span::styled(
+                change,
+                Style::default().fg(if improved { theme.correct } else { theme.error }).add_modifier(Modifier::BOLD),
+            ),
+        ]),
+    ])
+    .alignment(Alignment::Center)
+    .block(Block::default().borders(Borders::ALL).title(layout::stats_title(app, "Trend")));
+    f.render_widget(summary, chunks[0]);
+
+    let max_x = points.len().max(2) as f64;
+    let max_y = points.iter().map(|&(_, y)| y).fold(0.0, f64::max);
+    let (y_title, step) = match metric {
+        KeyMetric::ErrorRate => ("error %", 10.0),
+        KeyMetric::Latency => ("ms", 100.0),
+    };
+    // Round the y-axis up to a whole step
+    let max_y = ((max_y / step).ceil() * step).max(step);
+    let date = |i: usize| {
+        trend
+            .get(i)
+            .map(|(t, _)| history::format_timestamp(*t)[..10].to_string())
+            .unwrap_or_default()
+    };
+
+    let datasets = vec![
+        Dataset::default()
+            .name(y_title)
+            .marker(Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(theme.accent))
+            .data(&points),
+    ];
+    let chart = Chart::new(datasets)
+        .block(Block::default().borders(Borders::ALL).title("Per run"))
+        .x_axis(
+            Axis::default()
+                .title("run")
+                .style(Style::default().fg(theme.dim))
+                .bounds([1.0, max_x])
+                .labels([date(0), date(trend.len().saturating_sub(1))]),
+        )
+        .y_axis(
+            Axis::default()
+                .title(y_title)
+                .style(Style::default().fg(theme.dim))
+                .bounds([0.0, max_y])
+                .labels(["0".to_string(), format!("{}", (max_y / 2.0) as u64), format!("{}", max_y as u64)]),
+        );
+    f.render_widget(chart, chunks[1]);
+}
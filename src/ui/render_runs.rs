@@ -0,0 +1,60 @@
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+use crate::app::App;
+use crate::app::history;
+use super::layout;
+
+/// Lists past runs, newest first, so one can be picked for a replay.
+pub fn render(f: &mut Frame, app: &App, area: Rect) {
+    let theme = app.theme();
+    let block = Block::default().borders(Borders::ALL).title(layout::stats_title(app, "Past runs"));
+    if app.history.is_empty() {
+        let empty = Paragraph::new(vec![Line::from(""), Line::from("No tests recorded yet.")])
+            .alignment(Alignment::Center)
+            .block(block);
+        f.render_widget(empty, area);
+        return;
+    }
+
+    let (first, rows) = layout::list_window(block.inner(area), app.run_selection);
+
+    let header = Style::default().fg(theme.accent).add_modifier(Modifier::BOLD);
+    let mut lines = vec![Line::from(Span::styled(
+        format!(
+            "    {:<16} {:<8} {:<14} {:>6} {:>7} {:>7}  {}",
+            "DATE (UTC)", "MODE", "LANGUAGE", "WPM", "ACC", "TIME", "REPLAY"
+        ),
+        header,
+    ))];
+    for (row, &i) in app.runs_by_date().iter().enumerate().skip(first).take(rows) {
+        let run = &app.history[i];
+        let replayable = run.text.is_some() && !run.keystrokes.is_empty();
+        let text = format!(
+            "{:<16} {:<8} {:<14} {:>6.1} {:>6.1}% {:>6.1}s  {}",
+            history::format_timestamp(run.timestamp),
+            run.mode.label(),
+            run.language,
+            run.wpm,
+            run.accuracy,
+            run.duration_secs,
+            if replayable { "▶" } else { "-" },
+        );
+        lines.push(if row == app.run_selection {
+            Line::from(vec![
+                Span::styled("  → ", Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)),
+                Span::styled(text, Style::default().fg(theme.text).add_modifier(Modifier::BOLD)),
+            ])
+        } else {
+            let style = Style::default().fg(theme.dim);
+            let style = if replayable { style } else { style.add_modifier(Modifier::DIM) };
+            Line::from(vec![Span::raw("    "), Span::styled(text, style)])
+        });
+    }
+
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
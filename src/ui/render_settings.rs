@@ -1,23 +1,28 @@
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, Paragraph},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
     Frame,
 };
 use crate::app::{App, TestMode};
+use crate::app::engine::CharState;
+use crate::themes::Theme;
+use super::render_test::{caret_style, char_style, extra_style};
 
 pub fn render(f: &mut Frame, app: &App, area: Rect) {
+    let theme = app.theme();
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(3),
             Constraint::Min(0),
+            Constraint::Length(3),
         ])
         .split(area);
 
     let title = Paragraph::new("Settings")
-        .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
+        .style(Style::default().fg(theme.accent).add_modifier(Modifier::BOLD))
         .alignment(Alignment::Center)
         .block(Block::default().borders(Borders::ALL));
     f.render_widget(title, chunks[0]);
@@ -25,17 +30,40 @@ pub fn render(f: &mut Frame, app: &App, area: Rect) {
     let mode_text = match app.settings.test_mode {
         TestMode::Words => format!("Words ({})", app.settings.word_count),
         TestMode::Time => format!("Time ({}s)", app.settings.time_limit),
+        TestMode::Quote => format!("Quote ({})", app.settings.quote_length.label()),
+        TestMode::Custom => match &app.custom_text {
+            Some(text) => format!("Custom ({})", text.source),
+            None => "Custom (no text loaded)".to_string(),
+        },
+        TestMode::Code => format!("Code ({})", app.settings.code_language),
+        TestMode::Practice => format!("Practice ({} words)", app.settings.word_count),
     };
     let word_count_text = format!("{} words", app.settings.word_count);
     let time_limit_text = format!("{} seconds", app.settings.time_limit);
     let lines_text = format!("{} lines", app.settings.lines_to_display);
+    let on_off = |enabled: bool| if enabled { "On" } else { "Off" };
+    let number_rate_text = format!("{}% of words", app.settings.number_rate);
+    let section_text = match app.settings.custom_section_words {
+        0 => "Whole text".to_string(),
+        n => format!("{} words", n),
+    };
 
-    let settings_items = vec![
+    let settings_items = [
         ("Test Mode", mode_text.as_str()),
         ("Word Count", word_count_text.as_str()),
         ("Time Limit", time_limit_text.as_str()),
         ("Language", app.settings.language.as_str()),
         ("Display Lines", lines_text.as_str()),
+        ("Punctuation", on_off(app.settings.punctuation)),
+        ("Numbers", on_off(app.settings.numbers)),
+        ("Number Frequency", number_rate_text.as_str()),
+        ("Quote Length", app.settings.quote_length.label()),
+        ("Custom: Normalise Whitespace", on_off(app.settings.custom_normalize_whitespace)),
+        ("Custom: ASCII Only", on_off(app.settings.custom_ascii_only)),
+        ("Custom: Section Size", section_text.as_str()),
+        ("Code Language", app.settings.code_language.as_str()),
+        ("Ghost (Personal Best)", on_off(app.settings.ghost)),
+        ("Theme", app.settings.theme.as_str()),
     ];
 
     let items: Vec<ListItem> = settings_items
@@ -46,17 +74,17 @@ pub fn render(f: &mut Frame, app: &App, area: Rect) {
             
             let content = if is_selected {
                 Line::from(vec![
-                    Span::styled("→ ", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
-                    Span::styled(*label, Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+                    Span::styled("→ ", Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)),
+                    Span::styled(*label, Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)),
                     Span::raw(": "),
-                    Span::styled(*value, Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)),
+                    Span::styled(*value, Style::default().fg(theme.correct).add_modifier(Modifier::BOLD)),
                 ])
             } else {
                 Line::from(vec![
                     Span::raw("  "),
-                    Span::styled(*label, Style::default().fg(Color::White)),
+                    Span::styled(*label, Style::default().fg(theme.text)),
                     Span::raw(": "),
-                    Span::styled(*value, Style::default().fg(Color::Gray)),
+                    Span::styled(*value, Style::default().fg(theme.dim)),
                 ])
             };
             
@@ -66,7 +94,35 @@ pub fn render(f: &mut Frame, app: &App, area: Rect) {
 
     let list = List::new(items)
         .block(Block::default().borders(Borders::ALL).title("Configure Settings"))
-        .style(Style::default().fg(Color::White));
+        .style(Style::default().fg(theme.text));
+
+    // Scrolls to keep the selected setting in view on short terminals
+    let mut state = ListState::default().with_selected(Some(app.settings_selection));
+    f.render_stateful_widget(list, chunks[1], &mut state);
+
+    let preview = Paragraph::new(preview_line(theme))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL).title("Theme Preview"));
+    f.render_widget(preview, chunks[2]);
+}
 
-    f.render_widget(list, chunks[1]);
+/// A test typed part way through, with a mistake, a correction and an extra
+/// letter, so every text colour of the theme shows.
+fn preview_line(theme: &Theme) -> Line<'static> {
+    let parts = [
+        ("the qu", CharState::Correct),
+        ("i", CharState::Corrected),
+        ("ck br", CharState::Correct),
+        ("o", CharState::Incorrect),
+        ("wn", CharState::Correct),
+    ];
+    let mut spans: Vec<Span> = parts
+        .into_iter()
+        .map(|(text, state)| Span::styled(text, char_style(theme, state)))
+        .collect();
+    spans.push(Span::styled("s", extra_style(theme)));
+    spans.push(Span::styled(" ", char_style(theme, CharState::Correct)));
+    spans.push(Span::styled("f", caret_style(theme)));
+    spans.push(Span::styled("ox jumps over", char_style(theme, CharState::Pending)));
+    Line::from(spans)
 }
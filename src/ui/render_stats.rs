@@ -1,60 +1,67 @@
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
+    symbols::Marker,
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph},
+    widgets::{Axis, Block, Borders, Chart, Dataset, GraphType, Paragraph},
     Frame,
 };
-use crate::app::App;
+use crate::app::{App, TestMode};
 
 pub fn render(f: &mut Frame, app: &App, area: Rect) {
+    let theme = app.theme();
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(3),
-            Constraint::Min(0),
+            Constraint::Length(10),
+            Constraint::Min(8),
         ])
         .split(area);
 
     // Title
     let title = Paragraph::new("Test Complete!")
-        .style(Style::default().fg(Color::Green).add_modifier(Modifier::BOLD))
+        .style(Style::default().fg(theme.correct).add_modifier(Modifier::BOLD))
         .alignment(Alignment::Center)
         .block(Block::default().borders(Borders::ALL));
     f.render_widget(title, chunks[0]);
 
     // Stats display
+    let label = Style::default().fg(theme.accent).add_modifier(Modifier::BOLD);
+    let value = Style::default().fg(theme.text).add_modifier(Modifier::BOLD);
+    let counts = &app.char_counts;
     let stats_text = vec![
-        Line::from(""),
         Line::from(""),
         Line::from(vec![
-            Span::styled("WPM: ", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
-            Span::styled(
-                format!("{:.1}", app.wpm),
-                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
-            ),
+            Span::styled("WPM: ", label),
+            Span::styled(format!("{:.1}", app.wpm), value),
+            Span::raw("    "),
+            Span::styled("Raw: ", label),
+            Span::styled(format!("{:.1}", app.raw_wpm), value),
         ]),
-        Line::from(""),
         Line::from(vec![
-            Span::styled("Accuracy: ", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
-            Span::styled(
-                format!("{:.1}%", app.accuracy),
-                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
-            ),
+            Span::styled("Accuracy: ", label),
+            Span::styled(format!("{:.1}%", app.accuracy), value),
+            Span::raw("    "),
+            Span::styled("Consistency: ", label),
+            Span::styled(format!("{:.1}%", app.consistency), value),
         ]),
-        Line::from(""),
         Line::from(vec![
-            Span::styled("Errors: ", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+            Span::styled("Errors: ", label),
             Span::styled(
-                format!("{}", app.errors),
-                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                format!("{}", counts.incorrect + counts.extra + counts.missed),
+                Style::default().fg(theme.error).add_modifier(Modifier::BOLD),
             ),
+            Span::raw("    "),
+            Span::styled("Corrected: ", label),
+            Span::styled(format!("{}", counts.corrected), value),
         ]),
-        Line::from(""),
+        details_line(app),
+        ghost_line(app),
         Line::from(""),
         Line::from(Span::styled(
             "Press Enter or Esc to return to menu",
-            Style::default().fg(Color::Gray),
+            Style::default().fg(theme.dim),
         )),
     ];
 
@@ -62,4 +69,135 @@ pub fn render(f: &mut Frame, app: &App, area: Rect) {
         .alignment(Alignment::Center)
         .block(Block::default().borders(Borders::ALL).title("Results"));
     f.render_widget(stats, chunks[1]);
+
+    render_timeline(f, app, chunks[2]);
+}
+
+/// Describes what was typed: the quote and how it compares to the best
+/// previous attempt, or the test settings otherwise.
+fn details_line(app: &App) -> Line<'static> {
+    let theme = app.theme();
+    let dim = Style::default().fg(theme.dim);
+    let Some(quote) = &app.current_quote else {
+        let description = match (&app.custom_text, app.custom_section, &app.current_snippet) {
+            (_, _, Some(snippet)) => match app.symbol_accuracy {
+                Some(symbols) => format!("code · {} · symbols {:.1}%", snippet.source, symbols),
+                None => format!("code · {}", snippet.source),
+            },
+            (Some(text), Some((section, total)), _) => {
+                format!("custom · {} · section {}/{}", text.source, section, total)
+            }
+            (Some(text), None, _) if app.settings.test_mode == TestMode::Custom => {
+                format!("custom · {}", text.source)
+            }
+            _ => match app.settings.test_mode {
+                TestMode::Time => format!("time {}s · {}", app.settings.time_limit, app.settings.language),
+                TestMode::Practice if app.practice_targets.is_empty() => {
+                    format!("practice · {} · not enough keystroke data yet", app.settings.language)
+                }
+                TestMode::Practice => format!(
+                    "practice · {} · targeting {}",
+                    app.settings.language,
+                    app.practice_targets.join(", ")
+                ),
+                _ => format!("words {} · {}", app.settings.word_count, app.settings.language),
+            },
+        };
+        let description = match app.seed {
+            Some(seed) => format!("{} · seed {}", description, seed),
+            None => description,
+        };
+        return Line::from(Span::styled(description, dim));
+    };
+
+    let best = match app.previous_quote_best() {
+        Some(best) if app.wpm > best => Span::styled(
+            format!("  New best! (was {:.1})", best),
+            Style::default().fg(theme.correct).add_modifier(Modifier::BOLD),
+        ),
+        Some(best) => Span::styled(format!("  Best: {:.1}", best), dim),
+        None => Span::styled("  First attempt", dim),
+    };
+    Line::from(vec![
+        Span::styled(format!("— {} (#{})", quote.source, quote.id), Style::default().fg(theme.text)),
+        best,
+    ])
+}
+
+/// How the run compared with the ghost it raced, if any.
+fn ghost_line(app: &App) -> Line<'static> {
+    let theme = app.theme();
+    let Some(ghost) = &app.ghost else {
+        return Line::from("");
+    };
+    let margin = app.wpm - ghost.wpm;
+    let (text, colour) = if margin > 0.0 {
+        (format!("Beat the ghost ({}) by {:.1} wpm", ghost.label, margin), theme.correct)
+    } else if margin < 0.0 {
+        (format!("Lost to the ghost ({}) by {:.1} wpm", ghost.label, -margin), theme.error)
+    } else {
+        (format!("Tied with the ghost ({})", ghost.label), theme.accent)
+    };
+    Line::from(Span::styled(text, Style::default().fg(colour).add_modifier(Modifier::BOLD)))
+}
+
+fn render_timeline(f: &mut Frame, app: &App, area: Rect) {
+    let theme = app.theme();
+    let wpm: Vec<(f64, f64)> = app.timeline.iter().map(|s| (s.second as f64, s.wpm)).collect();
+    let raw: Vec<(f64, f64)> = app.timeline.iter().map(|s| (s.second as f64, s.raw_wpm)).collect();
+    // Error markers sit on the WPM line so they read as "stumbled here"
+    let errors: Vec<(f64, f64)> = app
+        .timeline
+        .iter()
+        .filter(|s| s.errors > 0)
+        .map(|s| (s.second as f64, s.wpm))
+        .collect();
+
+    let max_x = app.timeline.len().max(1) as f64;
+    let max_y = wpm
+        .iter()
+        .chain(raw.iter())
+        .map(|&(_, y)| y)
+        .fold(0.0, f64::max);
+    // Round the y-axis up to a multiple of 20 WPM
+    let max_y = ((max_y / 20.0).ceil() * 20.0).max(20.0);
+
+    let datasets = vec![
+        Dataset::default()
+            .name("raw")
+            .marker(Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(theme.dim))
+            .data(&raw),
+        Dataset::default()
+            .name("wpm")
+            .marker(Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(theme.accent))
+            .data(&wpm),
+        Dataset::default()
+            .name("errors")
+            .marker(Marker::Dot)
+            .graph_type(GraphType::Scatter)
+            .style(Style::default().fg(theme.error).add_modifier(Modifier::BOLD))
+            .data(&errors),
+    ];
+
+    let chart = Chart::new(datasets)
+        .block(Block::default().borders(Borders::ALL).title("Speed over time"))
+        .x_axis(
+            Axis::default()
+                .title("seconds")
+                .style(Style::default().fg(theme.dim))
+                .bounds([1.0, max_x])
+                .labels(["1".to_string(), format!("{}", max_x as u64)]),
+        )
+        .y_axis(
+            Axis::default()
+                .title("wpm")
+                .style(Style::default().fg(theme.dim))
+                .bounds([0.0, max_y])
+                .labels(["0".to_string(), format!("{}", (max_y / 2.0) as u64), format!("{}", max_y as u64)]),
+        );
+    f.render_widget(chart, area);
 }
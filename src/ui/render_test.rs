@@ -1,67 +1,182 @@
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph, Wrap},
+    widgets::{Block, Borders, Paragraph},
     Frame,
 };
-use crate::app::App;
+use crate::app::{App, AppMode};
+use crate::app::engine::{CharState, TypingEngine};
+use crate::themes::Theme;
+use super::text_layout::{self, VisualLine};
 
 pub fn render(f: &mut Frame, app: &App, area: Rect) {
+    let theme = app.theme();
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(3),
-            Constraint::Min(8),
+            Constraint::Length(app.settings.lines_to_display as u16 + 2),
             Constraint::Length(5),
+            Constraint::Min(0),
         ])
         .split(area);
 
-    let progress_text = if let Some(start) = app.start_time {
-        let elapsed = start.elapsed().as_secs();
-        format!("Time: {}s | Progress: {}/{}", elapsed, app.current_pos, app.test_text.len())
+    // A replay draws its own engine in place of the live one
+    let replay = app.replay.as_ref().filter(|_| app.mode == AppMode::Replay);
+    let engine = replay.map(|r| &r.engine).unwrap_or(&app.engine);
+
+    let progress_text = if let Some(replay) = replay {
+        let state = if replay.is_finished() {
+            " · finished"
+        } else if replay.paused {
+            " · paused"
+        } else {
+            ""
+        };
+        format!(
+            "Replay {}x{} | {:.1}s / {:.1}s | {:.0} wpm (final {:.1}) | Progress: {}/{}",
+            replay.speed,
+            state,
+            replay.position_ms / 1000.0,
+            replay.duration_ms / 1000.0,
+            replay.current_wpm(),
+            replay.wpm,
+            engine.pos(),
+            engine.len()
+        )
+    } else if let Some(start) = app.start_time {
+        let time = match app.time_remaining() {
+            Some(remaining) => format!("Time left: {}s", remaining),
+            None => format!("Time: {}s", start.elapsed().as_secs()),
+        };
+        let ghost = match &app.ghost {
+            Some(ghost) => {
+                let lead = engine.pos() as i64 - ghost.position.min(engine.len()) as i64;
+                match lead {
+                    0 => " | Level with ghost".to_string(),
+                    n if n > 0 => format!(" | Ahead of ghost by {}", n),
+                    n => format!(" | Behind ghost by {}", -n),
+                }
+            }
+            None => String::new(),
+        };
+        format!("{} | Progress: {}/{}{}", time, engine.pos(), engine.len(), ghost)
     } else {
         "Press any key to start...".to_string()
     };
 
     let progress = Paragraph::new(progress_text)
-        .style(Style::default().fg(Color::Cyan))
+        .style(Style::default().fg(theme.accent))
         .alignment(Alignment::Center)
         .block(Block::default().borders(Borders::ALL).title("Progress"));
     f.render_widget(progress, chunks[0]);
 
-    let mut spans = Vec::new();
-    
-    for (i, ch) in app.test_text.chars().enumerate() {
-        let style = if i < app.current_pos {
-            if i < app.current_input.len() {
-                let typed = app.current_input.chars().nth(i).unwrap();
-                if typed == ch {
-                    Style::default().fg(Color::Green)
-                } else {
-                    Style::default().fg(Color::Red).add_modifier(Modifier::UNDERLINED)
-                }
-            } else {
-                Style::default().fg(Color::Green)
-            }
-        } else if i == app.current_pos {
-            Style::default().fg(Color::Black).bg(Color::White).add_modifier(Modifier::BOLD)
-        } else {
-            Style::default().fg(Color::Gray)
-        };
-        
-        spans.push(Span::styled(ch.to_string(), style));
-    }
+    let title = match (&app.ghost, replay) {
+        (_, Some(_)) => "Replay".to_string(),
+        (Some(ghost), None) => format!("Type this text (racing {}, {:.1} wpm)", ghost.label, ghost.wpm),
+        (None, None) => "Type this text".to_string(),
+    };
+    let text_block = Block::default().borders(Borders::ALL).title(title);
+    let width = text_block.inner(chunks[1]).width as usize;
+    let layout = text_layout::wrap_words(engine.words(), width);
+    let caret = engine.caret();
+    let ghost = app
+        .ghost
+        .as_ref()
+        .filter(|_| replay.is_none())
+        .map(|g| engine.caret_at(g.position));
+    let first = text_layout::scroll_offset(
+        text_layout::line_of(&layout, caret.0),
+        layout.len(),
+        app.settings.lines_to_display,
+    );
 
-    let text_display = Paragraph::new(Line::from(spans))
-        .wrap(Wrap { trim: false })
-        .alignment(Alignment::Center)
-        .block(Block::default().borders(Borders::ALL).title("Type this text"));
+    let lines: Vec<Line> = layout
+        .iter()
+        .skip(first)
+        .take(app.settings.lines_to_display)
+        .map(|line| render_line(theme, engine, line, caret, ghost))
+        .collect();
+
+    let text_display = Paragraph::new(lines).block(text_block);
     f.render_widget(text_display, chunks[1]);
 
-    let input_display = Paragraph::new(app.current_input.as_str())
-        .style(Style::default().fg(Color::White))
+    let input_display = Paragraph::new(engine.input())
+        .style(Style::default().fg(theme.text))
         .alignment(Alignment::Center)
         .block(Block::default().borders(Borders::ALL).title("Your input"));
     f.render_widget(input_display, chunks[2]);
 }
+
+fn render_line(
+    theme: &Theme,
+    engine: &TypingEngine,
+    line: &VisualLine,
+    caret: (usize, usize),
+    ghost: Option<(usize, usize)>,
+) -> Line<'static> {
+    let caret_style = caret_style(theme);
+    let ghost_style = Style::default().fg(theme.accent).add_modifier(Modifier::REVERSED);
+    let (caret_word, caret_letter) = caret;
+    let words = engine.words();
+    let mut spans = vec![Span::raw(" ".repeat(line.indent))];
+
+    for &w in &line.words {
+        let word = &words[w];
+        for (i, slot) in word.chars.iter().enumerate() {
+            let style = if (w, i) == caret {
+                caret_style
+            } else if Some((w, i)) == ghost {
+                ghost_style
+            } else {
+                char_style(theme, slot.state)
+            };
+            spans.push(Span::styled(slot.expected.to_string(), style));
+        }
+        for ch in &word.extra {
+            spans.push(Span::styled(ch.to_string(), extra_style(theme)));
+        }
+
+        // Once a word is fully typed the caret sits on the separator after it
+        let caret_on_separator = w == caret_word && caret_letter >= word.chars.len();
+        let ghost_on_separator = ghost.is_some_and(|(gw, gl)| gw == w && gl >= word.chars.len());
+        let style = if caret_on_separator {
+            caret_style
+        } else if ghost_on_separator {
+            ghost_style
+        } else {
+            Style::default()
+        };
+        if word.separator.contains('\n') {
+            // Indentation after the break belongs to the next line
+            let marked = caret_on_separator || ghost_on_separator;
+            spans.push(Span::styled(if marked { "↵" } else { "" }, style));
+        } else if !word.separator.is_empty() {
+            spans.push(Span::styled(" ", style));
+            spans.push(Span::raw(" ".repeat(word.separator.chars().count() - 1)));
+        }
+    }
+
+    Line::from(spans)
+}
+
+/// Reversed, so the character shows in the background colour on top.
+pub(super) fn caret_style(theme: &Theme) -> Style {
+    Style::default().fg(theme.caret).add_modifier(Modifier::REVERSED | Modifier::BOLD)
+}
+
+/// Letters typed past the end of a word.
+pub(super) fn extra_style(theme: &Theme) -> Style {
+    Style::default().fg(theme.extra).add_modifier(Modifier::DIM)
+}
+
+pub(super) fn char_style(theme: &Theme, state: CharState) -> Style {
+    match state {
+        CharState::Pending => Style::default().fg(theme.dim),
+        CharState::Correct => Style::default().fg(theme.correct),
+        CharState::Incorrect => Style::default().fg(theme.error).add_modifier(Modifier::UNDERLINED),
+        CharState::Corrected => Style::default().fg(theme.accent),
+        CharState::Missed => Style::default().fg(theme.dim).add_modifier(Modifier::UNDERLINED),
+    }
+}
@@ -1,13 +1,16 @@
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph},
+    widgets::{Block, Borders, Paragraph, Tabs},
     Frame,
 };
 use crate::app::App;
+use crate::app::state::StatsView;
+use super::{render_keyboard, render_ngrams, render_runs};
 
 pub fn render(f: &mut Frame, app: &App, area: Rect) {
+    let theme = app.theme();
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -16,74 +19,95 @@ pub fn render(f: &mut Frame, app: &App, area: Rect) {
         ])
         .split(area);
 
-    let title = Paragraph::new("Your Statistics")
-        .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
-        .alignment(Alignment::Center)
-        .block(Block::default().borders(Borders::ALL));
-    f.render_widget(title, chunks[0]);
+    let views: Vec<&str> = StatsView::ALL.iter().map(|v| v.label()).collect();
+    let selected = StatsView::ALL.iter().position(|v| *v == app.stats_view).unwrap_or(0);
+    let tabs = Tabs::new(views)
+        .select(selected)
+        .style(Style::default().fg(theme.dim))
+        .highlight_style(Style::default().fg(theme.accent).add_modifier(Modifier::BOLD))
+        .block(Block::default().borders(Borders::ALL).title("Your Statistics"));
+    f.render_widget(tabs, chunks[0]);
+
+    match app.stats_view {
+        StatsView::Overview => render_overview(f, app, chunks[1]),
+        StatsView::Keyboard => render_keyboard::render(f, app, chunks[1]),
+        StatsView::Ngrams => render_ngrams::render(f, app, chunks[1]),
+        StatsView::Runs => render_runs::render(f, app, chunks[1]),
+    }
+}
 
+fn render_overview(f: &mut Frame, app: &App, area: Rect) {
+    let theme = app.theme();
     let hours = app.user_stats.total_time_seconds / 3600;
     let minutes = (app.user_stats.total_time_seconds % 3600) / 60;
     
+    let total_tests = match app.user_stats.imported_tests {
+        0 => app.user_stats.total_tests.to_string(),
+        n => format!("{} ({} imported)", app.user_stats.total_tests, n),
+    };
+
     let stats_text = vec![
         Line::from(""),
         Line::from(vec![
-            Span::styled("Total Tests: ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+            Span::styled("Total Tests: ", Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)),
             Span::styled(
-                format!("{}", app.user_stats.total_tests),
-                Style::default().fg(Color::White),
+                total_tests,
+                Style::default().fg(theme.text),
             ),
         ]),
         Line::from(""),
         Line::from(vec![
-            Span::styled("Average WPM: ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+            Span::styled("Average WPM: ", Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)),
             Span::styled(
                 format!("{:.1}", app.user_stats.average_wpm),
-                Style::default().fg(Color::Cyan),
+                Style::default().fg(theme.text),
             ),
         ]),
         Line::from(""),
         Line::from(vec![
-            Span::styled("Best WPM: ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+            Span::styled("Best WPM: ", Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)),
             Span::styled(
                 format!("{:.1}", app.user_stats.best_wpm),
-                Style::default().fg(Color::Green).add_modifier(Modifier::BOLD),
+                Style::default().fg(theme.correct).add_modifier(Modifier::BOLD),
             ),
         ]),
         Line::from(""),
         Line::from(vec![
-            Span::styled("Average Accuracy: ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+            Span::styled("Average Accuracy: ", Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)),
             Span::styled(
                 format!("{:.1}%", app.user_stats.average_accuracy),
-                Style::default().fg(Color::Cyan),
+                Style::default().fg(theme.text),
             ),
         ]),
         Line::from(""),
         Line::from(vec![
-            Span::styled("Total Words Typed: ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+            Span::styled("Total Words Typed: ", Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)),
             Span::styled(
                 format!("{}", app.user_stats.total_words_typed),
-                Style::default().fg(Color::White),
+                Style::default().fg(theme.text),
             ),
         ]),
         Line::from(""),
         Line::from(vec![
-            Span::styled("Total Time: ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+            Span::styled("Total Time: ", Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)),
             Span::styled(
                 format!("{}h {}m", hours, minutes),
-                Style::default().fg(Color::White),
+                Style::default().fg(theme.text),
             ),
         ]),
         Line::from(""),
         Line::from(""),
-        Line::from(Span::styled(
-            "Press Enter or Esc to return to menu",
-            Style::default().fg(Color::Gray),
-        )),
+        match &app.stats_status {
+            Some(status) => Line::from(Span::styled(status.as_str(), Style::default().fg(theme.correct))),
+            None => Line::from(Span::styled(
+                "Press Enter or Esc to return to menu",
+                Style::default().fg(theme.dim),
+            )),
+        },
     ];
 
     let stats = Paragraph::new(stats_text)
         .alignment(Alignment::Center)
         .block(Block::default().borders(Borders::ALL).title("Overall Statistics"));
-    f.render_widget(stats, chunks[1]);
+    f.render_widget(stats, area);
 }
@@ -0,0 +1,99 @@
+use crate::app::engine::Word;
+
+/// One screen line of the test text.
+#[derive(Debug, Clone, Default)]
+pub struct VisualLine {
+    /// Spaces before the first word, kept from indentation after a line break.
+    pub indent: usize,
+    /// Indices of the words on this line, in order.
+    pub words: Vec<usize>,
+}
+
+/// Breaks the words into lines no wider than `width`, never splitting a word.
+///
+/// Line breaks in the text always start a new line. A word longer than the
+/// whole width gets a line of its own and is clipped when drawn. Each word
+/// keeps room for the first cell of its separator, where the caret sits
+/// once the word is typed.
+pub fn wrap_words(words: &[Word], width: usize) -> Vec<VisualLine> {
+    let width = width.max(1);
+    let mut lines = vec![VisualLine::default()];
+    let mut used = 0;
+
+    for (i, word) in words.iter().enumerate() {
+        let word_width = word.chars.len() + word.extra.len();
+        let caret_cell = usize::from(!word.separator.is_empty());
+        let line = lines.last_mut().unwrap();
+        if !line.words.is_empty() && used + word_width + caret_cell > width {
+            lines.push(VisualLine::default());
+            used = 0;
+        }
+
+        let line = lines.last_mut().unwrap();
+        line.words.push(i);
+        used += word_width;
+
+        match word.separator.rfind('\n') {
+            Some(pos) => {
+                let indent = word.separator[pos + 1..].chars().count();
+                lines.push(VisualLine { indent, words: Vec::new() });
+                used = indent;
+            }
+            None => used += word.separator.chars().count(),
+        }
+    }
+
+    lines
+}
+
+/// Index of the line holding word `word`.
+pub fn line_of(lines: &[VisualLine], word: usize) -> usize {
+    lines
+        .iter()
+        .rposition(|l| l.words.first().is_some_and(|&first| first <= word))
+        .unwrap_or(0)
+}
+
+/// First line to show so the caret line stays on the second row, or the
+/// only row when one line is shown. Near the end the last `visible` lines
+/// are shown, so the area stays full.
+pub fn scroll_offset(caret_line: usize, line_count: usize, visible: usize) -> usize {
+    if visible <= 1 {
+        return caret_line;
+    }
+    caret_line.saturating_sub(1).min(line_count.saturating_sub(visible))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::engine::TypingEngine;
+
+    fn wrap(text: &str, width: usize) -> Vec<Vec<usize>> {
+        let engine = TypingEngine::new(text);
+        wrap_words(engine.words(), width).into_iter().map(|l| l.words).collect()
+    }
+
+    #[test]
+    fn separator_after_a_full_line_wraps() {
+        assert_eq!(wrap("hello abcd efg", 10), [vec![0], vec![1, 2]]);
+        // The last word has no separator, so it may fill the line
+        assert_eq!(wrap("hello abcd", 10), [vec![0, 1]]);
+    }
+
+    #[test]
+    fn line_breaks_start_a_new_line() {
+        let engine = TypingEngine::new("fn {\n    x");
+        let lines = wrap_words(engine.words(), 40);
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[1].indent, 4);
+        assert_eq!(lines[1].words, [2]);
+    }
+
+    #[test]
+    fn one_visible_line_follows_the_caret() {
+        assert_eq!(scroll_offset(3, 5, 1), 3);
+        assert_eq!(scroll_offset(3, 5, 3), 2);
+        assert_eq!(scroll_offset(4, 5, 3), 2);
+    }
+}